@@ -8,6 +8,8 @@ local udp = nil
 local HZ = 10
 local DT = 1.0 / HZ
 local lastSent = 0
+-- frame counter, so the receiver can count lost packets
+local seq = 0
 
 -- ------------- helpers -------------
 local function log(msg)
@@ -162,6 +164,29 @@ local function get_engine()
 	}
 end
 
+-- Internal + external fuel mass in kg; nil if the airframe doesn't report it
+local function get_fuel_kg()
+	local e = LoGetEngineInfo() or {}
+	local int, ext = N(e.fuel_internal), N(e.fuel_external)
+	if int == nil and ext == nil then
+		return nil
+	end
+	return (int or 0) + (ext or 0)
+end
+
+-- Static pressure in Pa (LoGetBasicAtmospherePressure reports mm Hg); nil if unavailable
+local function get_static_pa()
+	if type(LoGetBasicAtmospherePressure) ~= "function" then
+		return nil
+	end
+	local ok, mmhg = pcall(LoGetBasicAtmospherePressure)
+	if not ok then
+		return nil
+	end
+	local p = N(mmhg)
+	return p and p * 133.322
+end
+
 -- ------------- mech normalization -------------
 -- Coerce number/bool/table to 0..1
 local function to_ratio(v)
@@ -250,6 +275,7 @@ function LuaExportStart()
 		log("LuaSocket missing")
 	end
 	lastSent = 0
+	seq = 0
 end
 
 function LuaExportBeforeNextFrame()
@@ -262,6 +288,7 @@ function LuaExportAfterNextFrame()
 		return
 	end
 	lastSent = t
+	seq = seq + 1
 
 	local self = LoGetSelfData() or {}
 	local LLA = self.LatLongAlt or {}
@@ -269,6 +296,8 @@ function LuaExportAfterNextFrame()
 	local accel = LoGetAccelerationUnits() or {}
 
 	local payload = {
+		seq = seq,
+
 		-- flight baseline
 		name = self.Name or "",
 		lat = LLA.Lat,
@@ -280,6 +309,8 @@ function LuaExportAfterNextFrame()
 		mach = LoGetMachNumber(),
 		aoa_rad = LoGetAngleOfAttack(),
 		vv_ms = LoGetVerticalVelocity(),
+		fuel_kg = get_fuel_kg(),
+		p_static_pa = get_static_pa(),
 		att = { pitch = pitch, bank = bank, yaw = yaw },
 		accel = { x = accel.x, y = accel.y, z = accel.z },
 
@@ -1,4 +1,5 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use clap::Parser;
 use crossterm::{
     event::{self, Event, KeyCode as TermKeyCode, KeyEvent, KeyModifiers},
     execute,
@@ -8,390 +9,4553 @@ use crossterm::{
 use evdev::{Device, EventType, KeyCode};
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph, Sparkline, Wrap},
+    symbols::Marker,
+    widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Sparkline, SparklineBar,
+        Wrap,
+        canvas::{Canvas, Line as CanvasLine},
+    },
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     fs,
-    io::{self, Stdout},
-    path::PathBuf,
+    io::{self, BufWriter, Stdout, Write},
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
-use tokio::{net::UdpSocket, sync::watch, task, time::sleep};
+#[cfg(unix)]
+use tokio::net::UnixDatagram;
+use tokio::{
+    io::{AsyncReadExt, AsyncSeekExt},
+    net::UdpSocket,
+    sync::{mpsc, watch},
+    task,
+    time::sleep,
+};
 
 const BUF: usize = 8192;
+// How often a tailed file is checked for new data, truncation, or rotation.
+const TAIL_POLL_MS: u64 = 100;
+// Frames queued for the recorder; beyond this they are dropped rather than stall input.
+const RECORD_QUEUE: usize = 4096;
+// A crash loses at most this much of a recording.
+const RECORD_FLUSH_MS: u64 = 1000;
+// --replay speeds stepped through with `-` / `+`, and the `[` / `]` seek step.
+const REPLAY_SPEEDS: [f64; 4] = [0.5, 1.0, 2.0, 4.0];
+const REPLAY_SEEK_MS: f64 = 10_000.0;
+// Default redraw interval; --tick-ms overrides it.
 const TICK_MS: u64 = 100;
-const HISTORY: usize = 300;
+// Shortest --tick-ms; faster redraws only burn CPU.
+const MIN_TICK_MS: u64 = 10;
+// Input poll interval while idle; keys still feel responsive.
+const IDLE_POLL_MS: u64 = 50;
+// seq numbers this close to the last one count as late frames or a rollover, not a restart.
+const SEQ_WINDOW: u64 = 1024;
+// A forward jump past this many frames (over a day at 10 Hz) is a restart, not loss.
+const SEQ_MAX_GAP: u64 = 1 << 20;
+// Retained chart samples: 5 minutes at the exporter's 10 Hz, for charts.span_secs.
+// --history overrides it.
+const HISTORY: usize = 3000;
+// Tapes need room for "[12345]" plus borders, and the Flight text needs to stay readable.
+const TAPE_W: u16 = 9;
+const TAPE_MIN_PANE_W: u16 = 48;
+const GMETER_W: u16 = 7;
+const GMETER_MIN_PANE_W: u16 = 40;
+// Gauge scale top when no limits.g_max is configured.
+const GMETER_DEFAULT_TOP: f64 = 10.0;
+// Att pane needs this much inner room before the horizon is drawn under the text.
+const HORIZON_MIN_INNER_H: u16 = 9;
+const HORIZON_MIN_INNER_W: u16 = 16;
+// Pitch visible from the center of the horizon to its top/bottom edge, degrees.
+const HORIZON_RANGE_DEG: f64 = 20.0;
+// How far ahead the trend vector projects the flight path, seconds.
+const TREND_LEAD_S: f64 = 2.0;
+// Flight-path-angle samples kept for the trend rate (~1 s at 10 Hz).
+const FPA_HIST: usize = 10;
+// IAS trend caret: acceleration over the last second of chart history, projected this
+// far ahead; below the smallest step (kt) no caret shows, each further step adds one.
+const IAS_TREND_WINDOW: Duration = Duration::from_secs(1);
+const IAS_TREND_LEAD_S: f64 = 5.0;
+const IAS_TREND_STEPS_KT: [f64; 3] = [2.0, 10.0, 20.0];
 const INPUT_LOG_CAP: usize = 200;
 
 // We still track ABS for logging context, but mapping no longer depends on it.
+#[cfg_attr(not(feature = "wacom"), allow(dead_code))]
 const SIDE_TIMEOUT_MS: u128 = 250;
 
+// Packet and byte rates in the Stats pane are averaged over this window.
+const RX_RATE_WINDOW: Duration = Duration::from_secs(1);
+// Characters of a rejected frame kept for the Stats pane.
+const RX_EXCERPT_CHARS: usize = 60;
+// A field group that hasn't been in a packet for this long marks its panes with '*'.
+const FIELD_STALE_MS: u128 = 2000;
+// How long a header message (e.g. a snapshot's filename) stays up.
+const FLASH_MS: u128 = 3000;
+// A frame from another airframe is tracked alongside unless the shown one has been
+// quiet this long, in which case it takes over the display (a slot change).
+const AIRFRAME_FOLLOW_MS: u128 = 2000;
+
 // ---------------- Telemetry model ----------------
 
-#[derive(Debug, Clone, Deserialize, Default)]
+// Missing fields are skipped on output, so a serialized frame uses the exporter's keys and
+// parses back to the same value.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct Telemetry {
+    #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     lat: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     lon: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     alt_msl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     alt_agl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     ias_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tas_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     mach: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     aoa_rad: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     vv_ms: Option<f64>,
-    #[serde(default)]
+    /// Internal + external fuel mass, when the airframe exposes it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fuel_kg: Option<f64>,
+    /// Static (ambient) pressure at the aircraft, for pressure altitude.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    p_static_pa: Option<f64>,
+    /// Outside air temperature, for density altitude.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oat_c: Option<f64>,
+    /// Exporter frame counter, rising by one per frame, for counting lost packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seq: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     att: Option<Att>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     accel: Option<Accel>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     engine: Option<Engine>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     mech: Option<Mech>,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+impl Telemetry {
+    /// Flight path angle from vertical and true airspeed.
+    fn fpa_rad(&self) -> Option<f64> {
+        let (vv, tas) = (self.vv_ms?, self.tas_ms?);
+        (tas > 1.0).then(|| (vv / tas).clamp(-1.0, 1.0).asin())
+    }
+
+    fn has_flight(&self) -> bool {
+        [
+            self.ias_ms,
+            self.alt_msl,
+            self.mach,
+            self.vv_ms,
+            self.tas_ms,
+        ]
+        .iter()
+        .any(Option::is_some)
+    }
+}
+
+/// Merge a sparse JSON patch into `dst`: keys absent from `patch` keep their previous
+/// value, explicit `null`s clear them, and nested objects merge key by key. This is
+/// what lets streams carrying different subsets (flight vs systems) coexist.
+fn merge_json(dst: &mut Value, patch: Value) {
+    match (dst, patch) {
+        (Value::Object(dst), Value::Object(patch)) => {
+            for (k, v) in patch {
+                match dst.get_mut(&k) {
+                    Some(slot) if slot.is_object() && v.is_object() => merge_json(slot, v),
+                    _ => {
+                        dst.insert(k, v);
+                    }
+                }
+            }
+        }
+        (dst, patch) => *dst = patch,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct Att {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pitch: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     bank: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     yaw: Option<f64>,
 }
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct Accel {
+    #[serde(skip_serializing_if = "Option::is_none")]
     x: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     y: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     z: Option<f64>,
 }
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[allow(non_snake_case)] // field names match the exporter's JSON keys
 struct Pair {
+    #[serde(skip_serializing_if = "Option::is_none")]
     L: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     R: Option<f64>,
 }
-#[derive(Debug, Clone, Deserialize, Default)]
+
+impl Pair {
+    /// L − R; None unless both sides report (so single engines never show one).
+    fn split(&self) -> Option<f64> {
+        Some(self.L? - self.R?)
+    }
+}
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct Engine {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     rpm: Option<Pair>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     thrtl: Option<Pair>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     thrtl_est: Option<bool>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     noz: Option<Pair>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     noz_present: Option<bool>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     temp: Option<Pair>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     fuelf: Option<Pair>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     map: Option<Pair>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     map_present: Option<bool>,
 }
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct Mech {
+    #[serde(skip_serializing_if = "Option::is_none")]
     gear: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     flaps: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     airbrake: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     hook: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     wing: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     wow: Option<f64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     wow_guess: Option<bool>,
 }
 
-// ---------------- UI state ----------------
+// ---------------- CLI ----------------
 
-impl Default for Pane {
-    fn default() -> Self {
-        Pane::Flight
+/// Terminal dashboard for DCS telemetry.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Print device detection details (e.g. each Wacom candidate tried) to stderr.
+    #[arg(short, long)]
+    verbose: bool,
+    /// Disable all colors (NO_COLOR in the environment does the same).
+    #[arg(long)]
+    no_color: bool,
+    /// Keep a one-line IAS/ALT/Mach/AoA/G summary in this file (for stream overlays).
+    #[arg(long, value_name = "PATH")]
+    status_line: Option<PathBuf>,
+    /// Run without the TUI, printing one status line per packet to stdout.
+    #[arg(long)]
+    headless: bool,
+    /// Append every parsed packet to this JSONL file, with receive timestamps.
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+    /// Record at most this many frames per second (evenly spaced in time).
+    #[arg(long, value_name = "HZ", requires = "record", value_parser = parse_hz)]
+    record_rate: Option<f64>,
+    /// Start a new part (<name>.1.jsonl, <name>.2.jsonl, ...) once a recording file
+    /// reaches this many megabytes.
+    #[arg(long, value_name = "MB", requires = "record", value_parser = parse_mb)]
+    record_max_mb: Option<u64>,
+    /// Resend every received datagram, unmodified, to this address (a telemetry tee).
+    #[arg(long, value_name = "ADDR:PORT")]
+    forward: Option<String>,
+    /// Telemetry source: udp:<addr:port>, unix:<socket path> or file:<log path>. Repeat to
+    /// merge several streams [default: udp:<--bind>:<--port>]
+    #[arg(long, value_name = "SRC", value_parser = parse_input)]
+    input: Vec<Input>,
+    /// UDP port to listen on without --input.
+    #[arg(long, env = "PORT", default_value_t = 5010, value_parser = clap::value_parser!(u16).range(1..))]
+    port: u16,
+    /// Address to listen on without --input, e.g. 0.0.0.0 for an exporter on another machine.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+    bind: IpAddr,
+    /// Chart samples kept per chart, for charts.span_secs and the header stats.
+    #[arg(long, value_name = "SAMPLES", default_value_t = HISTORY, value_parser = parse_history)]
+    history: usize,
+    /// Redraw interval in milliseconds (at least 10).
+    #[arg(long, value_name = "MS", default_value_t = TICK_MS, value_parser = parse_tick_ms)]
+    tick_ms: u64,
+    /// For file: inputs, replay what's already in the file before following it.
+    #[arg(long)]
+    from_start: bool,
+    /// How JSON frames are delimited in UDP and Unix datagrams: one per line, or each
+    /// after a 4-byte big-endian length. File and serial inputs are always line-based.
+    #[arg(long, value_enum, default_value_t = Framing::Newline)]
+    framing: Framing,
+    /// Validate the config file (and FOCUS_COLOR), report errors, and exit.
+    #[arg(long)]
+    check_config: bool,
+    /// Write a commented default config to the standard location, then exit.
+    #[arg(long)]
+    init_config: bool,
+    /// List input devices (path, name, event types), marking likely Wacom pads, then exit.
+    #[cfg(feature = "wacom")]
+    #[arg(long)]
+    list_devices: bool,
+    /// With --init-config, replace an existing file.
+    #[arg(long, requires = "init_config")]
+    force: bool,
+    /// Generate synthetic telemetry instead of listening (screenshots, UI work).
+    #[arg(long, conflicts_with = "input")]
+    demo: bool,
+    /// Replay two --record files in step, with B's charts drawn over A's.
+    #[arg(long, num_args = 2, value_names = ["A", "B"], conflicts_with_all = ["input", "demo"])]
+    compare: Option<Vec<PathBuf>>,
+    /// Play back a --record file at its recorded pace, with pause, speed and seek keys.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["input", "demo", "compare"])]
+    replay: Option<PathBuf>,
+    /// In headless mode, print one avg/max summary line per interval instead.
+    #[arg(long, value_name = "SECS", requires = "headless", value_parser = parse_secs)]
+    summary_interval: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Framing {
+    Newline,
+    /// u32 big-endian payload length, then the payload; repeated to fill the datagram.
+    Length,
+}
+
+#[derive(Debug, Clone)]
+enum Input {
+    Udp(String),
+    /// Local datagram socket, e.g. for an exporter running under Wine/Proton on the same box.
+    #[cfg(unix)]
+    Unix(PathBuf),
+    /// Growing log file of newline-delimited JSON, followed like `tail -F`.
+    File(PathBuf),
+    /// Newline-delimited JSON over a serial link from dedicated hardware.
+    #[cfg(feature = "serial")]
+    Serial {
+        path: String,
+        baud: u32,
+    },
+}
+
+fn parse_input(s: &str) -> Result<Input, String> {
+    if let Some(addr) = s.strip_prefix("udp:") {
+        return Ok(Input::Udp(addr.to_string()));
+    }
+    if let Some(path) = s.strip_prefix("file:") {
+        return Ok(Input::File(PathBuf::from(path)));
+    }
+    #[cfg(unix)]
+    if let Some(path) = s.strip_prefix("unix:") {
+        return Ok(Input::Unix(PathBuf::from(path)));
     }
+    #[cfg(feature = "serial")]
+    if let Some(rest) = s.strip_prefix("serial:") {
+        let (path, baud) = rest
+            .rsplit_once(':')
+            .ok_or_else(|| format!("expected serial:<path>:<baud>, got `{s}`"))?;
+        let baud = baud
+            .parse()
+            .map_err(|_| format!("invalid baud rate `{baud}` in `{s}`"))?;
+        return Ok(Input::Serial {
+            path: path.to_string(),
+            baud,
+        });
+    }
+    Err(format!(
+        "unsupported input `{s}` (expected udp:<addr:port>, unix:<path>, file:<path> or serial:<path>:<baud>)"
+    ))
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Pane {
-    Flight = 0,
-    Att = 1,
-    Systems = 2,
-    Inputs = 3,
-    IasChart = 4,
-    AltChart = 5,
+fn parse_hz(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(v) if v > 0.0 && v.is_finite() => Ok(v),
+        _ => Err(format!("expected a positive rate in Hz, got `{s}`")),
+    }
 }
-const PANE_COUNT: usize = 6;
 
-impl Pane {
-    fn from_index(i: usize) -> Pane {
-        match i {
-            0 => Pane::Flight,
-            1 => Pane::Att,
-            2 => Pane::Systems,
-            3 => Pane::Inputs,
-            4 => Pane::IasChart,
-            _ => Pane::AltChart,
-        }
+fn parse_history(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(v) if v > 0 => Ok(v),
+        _ => Err(format!("expected a positive number of samples, got `{s}`")),
     }
-    fn index(self) -> usize {
-        self as usize
+}
+
+fn parse_tick_ms(s: &str) -> Result<u64, String> {
+    match s.parse::<u64>() {
+        Ok(v) if v >= MIN_TICK_MS => Ok(v),
+        _ => Err(format!("expected at least {MIN_TICK_MS} ms, got `{s}`")),
     }
 }
 
-#[derive(Debug, Clone, Default)]
-struct UiState {
-    last: Telemetry,
-    ias_hist: VecDeque<f64>,
-    alt_hist: VecDeque<f64>,
-    input_log: VecDeque<String>,
-    focused: Pane,
-    fullscreen: Option<Pane>,
+fn parse_mb(s: &str) -> Result<u64, String> {
+    match s.parse::<u64>() {
+        Ok(v) if v > 0 => Ok(v),
+        _ => Err(format!(
+            "expected a positive number of megabytes, got `{s}`"
+        )),
+    }
 }
 
-// ---------------- Small helpers ----------------
+fn parse_secs(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>() {
+        Ok(v) if v > 0.0 && v.is_finite() => Ok(Duration::from_secs_f64(v)),
+        _ => Err(format!("expected a positive number of seconds, got `{s}`")),
+    }
+}
 
-/// Try to open a Wacom pad **once**. If not found, return None (don’t block).
-#[cfg(feature = "wacom")]
-fn try_open_wacom_pad_now() -> Option<(String, Device)> {
-    if let Some(t) = open_wacom_from_env() {
-        return Some(t);
+// ---------------- Config ----------------
+
+/// Optional TOML config. Every section is optional; missing keys use defaults.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    charts: ChartsConfig,
+    limits: Limits,
+    alerts: Alerts,
+    smoothing: Smoothing,
+    idle: IdleConfig,
+    redraw: RedrawConfig,
+    screensaver: ScreensaverConfig,
+    stale: StaleConfig,
+    units: Units,
+    angles: AngleUnit,
+    no_color: bool,
+    colors: Colors,
+    /// Pane `h`/Home returns focus to.
+    home: Pane,
+    flight_log: FlightLogConfig,
+    wacom: WacomConfig,
+    diagnostics: DiagnosticsConfig,
+    layout: LayoutConfig,
+    splash: SplashConfig,
+    header: HeaderConfig,
+    state: StateConfig,
+    /// Redraw these panes at most once per this many ms instead of every tick.
+    refresh_ms: HashMap<Pane, u64>,
+    /// Derived values for the Computed pane: name -> arithmetic over telemetry fields.
+    computed: BTreeMap<String, Computed>,
+    /// Per-module overrides, keyed by the exporter's airframe name (e.g. "Su-25T").
+    airframes: BTreeMap<String, Airframe>,
+    #[serde(skip)]
+    theme: Theme,
+    /// Chart samples kept (--history); None = HISTORY.
+    #[serde(skip)]
+    history: Option<usize>,
+}
+
+/// Per-value color overrides. Unset values keep the terminal's default color.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Colors {
+    flight: FlightColors,
+    charts: ChartColors,
+    /// How the focused pane stands out.
+    focus_style: FocusStyle,
+    /// Background for focus_style = "tint".
+    focus_tint: ConfColor,
+    /// Line color of the second recording with --compare.
+    compare: ConfColor,
+    /// `[fraction of limits.g_max, color]` stops the G gauge and value blend through.
+    g_gradient: Vec<(f64, ConfColor)>,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Colors {
+            flight: FlightColors::default(),
+            charts: ChartColors::default(),
+            focus_style: FocusStyle::default(),
+            focus_tint: ConfColor(Color::Rgb(0x30, 0x30, 0x30)),
+            compare: ConfColor(Color::LightMagenta),
+            g_gradient: vec![
+                (0.0, ConfColor(Color::Green)),
+                (0.75, ConfColor(Color::Yellow)),
+                (0.9, ConfColor(Color::Red)),
+            ],
+        }
     }
-    // Prefer stable by-id paths
-    if let Ok(entries) = fs::read_dir("/dev/input/by-id") {
-        for ent in entries.flatten() {
-            let p: PathBuf = ent.path();
-            if let Ok(tgt) = fs::canonicalize(&p) {
-                let name = p
-                    .file_name()
-                    .map(|s| s.to_string_lossy())
-                    .unwrap_or_default();
-                if name.contains("Wacom")
-                    && name.to_ascii_lowercase().contains("pad")
-                    && name.contains("event")
-                {
-                    if let Ok(d) = Device::open(&tgt) {
-                        return Some((tgt.display().to_string(), d));
-                    }
+}
+
+/// Color at `x` along `stops` (rising positions): RGB blended between the two stops
+/// around it, the end color past either end. Named colors blend via their xterm
+/// values; indexed ones can't, and switch at the stop instead.
+fn gradient(stops: &[(f64, ConfColor)], x: f64) -> Color {
+    let Some(&(_, ConfColor(mut color))) = stops.first() else {
+        return Color::Reset;
+    };
+    for w in stops.windows(2) {
+        let ((x0, ConfColor(c0)), (x1, ConfColor(c1))) = (w[0], w[1]);
+        if x >= x1 {
+            color = c1;
+            continue;
+        }
+        if x > x0 {
+            let t = (x - x0) / (x1 - x0);
+            color = match (rgb(c0), rgb(c1)) {
+                (Some(a), Some(b)) => {
+                    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+                    Color::Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
                 }
-            }
+                _ => c0,
+            };
         }
+        break;
     }
-    // Fallback scan of /dev/input
-    if let Ok(entries) = fs::read_dir("/dev/input") {
-        for ent in entries.flatten() {
-            let p = ent.path();
-            let fname = p
-                .file_name()
-                .map(|s| s.to_string_lossy())
-                .unwrap_or_default();
-            if !fname.starts_with("event") {
-                continue;
-            }
-            if let Ok(d) = Device::open(&p) {
-                let n = d.name().unwrap_or("");
-                if n.contains("Wacom") && n.contains("Pad") {
-                    return Some((p.display().to_string(), d));
-                }
-            }
+    color
+}
+
+fn rgb(c: Color) -> Option<(u8, u8, u8)> {
+    Some(match c {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct FlightColors {
+    ias: Option<ConfColor>,
+    alt: Option<ConfColor>,
+    mach: Option<ConfColor>,
+    vv: Option<ConfColor>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum FocusStyle {
+    /// Border in the focus color.
+    #[default]
+    Border,
+    /// Title drawn inverted: black on the focus color.
+    Title,
+    /// Whole pane on colors.focus_tint.
+    Tint,
+}
+
+/// Bar/line color per chart, so charts are told apart at a glance.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ChartColors {
+    ias: Option<ConfColor>,
+    alt: Option<ConfColor>,
+    mach: Option<ConfColor>,
+    ps: Option<ConfColor>,
+    vv: Option<ConfColor>,
+    g: Option<ConfColor>,
+}
+
+impl ChartColors {
+    fn get(&self, pane: Pane) -> Option<ConfColor> {
+        match pane {
+            Pane::IasChart => self.ias,
+            Pane::AltChart => self.alt,
+            Pane::MachChart => self.mach,
+            Pane::PsChart => self.ps,
+            Pane::VvChart => self.vv,
+            Pane::GChart => self.g,
+            _ => None,
         }
     }
-    None
 }
 
-fn push_hist(q: &mut VecDeque<f64>, v: f64, cap: usize) {
-    q.push_back(v);
-    while q.len() > cap {
-        q.pop_front();
+/// A color name (`cyan`, `lightgreen`, ...) or `#rrggbb`, checked when the config loads.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+struct ConfColor(Color);
+
+impl TryFrom<String> for ConfColor {
+    type Error = String;
+    fn try_from(s: String) -> Result<Self, String> {
+        s.parse()
+            .map(ConfColor)
+            .map_err(|_| format!("`{s}` is not a color name or #rrggbb"))
     }
 }
-fn push_log(q: &mut VecDeque<String>, line: String) {
-    q.push_back(line);
-    while q.len() > INPUT_LOG_CAP {
-        q.pop_front();
+
+/// Warning thresholds. Unset limits disable the corresponding warning.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct Limits {
+    low_fuel_kg: Option<f64>,
+    /// Positive load factor (accel.y) limit.
+    g_max: Option<f64>,
+    /// Ground-proximity floor: warn below this AGL, but only while descending.
+    min_agl_m: Option<f64>,
+    /// Twin-engine L/R differences (RPM %, TEMP) above which the split turns red.
+    rpm_split: Option<f64>,
+    temp_split: Option<f64>,
+    /// Combined AoA/G envelope: `[aoa_deg, g_max]` points by rising AoA, interpolated
+    /// linearly and held flat past either end.
+    envelope: Option<Vec<[f64; 2]>>,
+    /// Climb or descent rate (m/s, from successive alt_msl samples) that, held for
+    /// `alt_rate_samples` samples in a row (default 5), raises ALT RATE.
+    alt_rate_max: Option<f64>,
+    alt_rate_samples: Option<u32>,
+}
+
+// Consecutive over-rate samples for ALT RATE when alt_rate_samples is unset.
+const ALT_RATE_SAMPLES: u32 = 5;
+// Altitude samples closer together than this are merged rather than differenced.
+const ALT_RATE_MIN_DT: Duration = Duration::from_millis(20);
+
+impl Limits {
+    /// These limits with any set in `over` taking precedence.
+    fn overlaid(&self, over: &Limits) -> Limits {
+        Limits {
+            low_fuel_kg: over.low_fuel_kg.or(self.low_fuel_kg),
+            g_max: over.g_max.or(self.g_max),
+            min_agl_m: over.min_agl_m.or(self.min_agl_m),
+            rpm_split: over.rpm_split.or(self.rpm_split),
+            temp_split: over.temp_split.or(self.temp_split),
+            envelope: over.envelope.clone().or_else(|| self.envelope.clone()),
+            alt_rate_max: over.alt_rate_max.or(self.alt_rate_max),
+            alt_rate_samples: over.alt_rate_samples.or(self.alt_rate_samples),
+        }
+    }
+
+    /// Envelope G limit at this AoA (degrees), if an envelope is configured.
+    fn envelope_g(&self, aoa_deg: f64) -> Option<f64> {
+        let pts = self.envelope.as_deref()?;
+        let (first, last) = (pts.first()?, pts.last()?);
+        if aoa_deg <= first[0] {
+            return Some(first[1]);
+        }
+        if aoa_deg >= last[0] {
+            return Some(last[1]);
+        }
+        pts.windows(2).find_map(|w| {
+            let ([a0, g0], [a1, g1]) = (w[0], w[1]);
+            (aoa_deg <= a1).then(|| g0 + (g1 - g0) * (aoa_deg - a0) / (a1 - a0))
+        })
     }
 }
-fn last_n_scaled(src: &VecDeque<f64>, n: usize, scale: f64) -> Vec<u64> {
-    let len = src.len();
-    let start = len.saturating_sub(n);
-    src.iter()
-        .skip(start)
-        .take(n)
-        .map(|v| (*v * scale).max(0.0) as u64)
-        .collect()
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct FlightLogConfig {
+    /// Also log gear, flaps, airbrake, hook and wing changes, not just takeoffs/landings.
+    mech: bool,
 }
-fn last_n(src: &VecDeque<f64>, n: usize) -> Vec<u64> {
-    let len = src.len();
-    let start = len.saturating_sub(n);
-    src.iter()
-        .skip(start)
-        .take(n)
-        .map(|v| v.max(0.0) as u64)
-        .collect()
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LayoutConfig {
+    /// Top-row panes, left to right; any of flight/att/systems. Omitted ones aren't shown.
+    top: Vec<Pane>,
+    /// Width of each `top` pane in percent (summing to 100). Unset = equal widths.
+    top_widths: Option<Vec<u16>>,
+    /// Start in the dense numbers-only layout; `D` toggles.
+    dense: bool,
 }
-fn fmt_ts(ts: SystemTime) -> (u64, u32) {
-    match ts.duration_since(UNIX_EPOCH) {
-        Ok(d) => ((d.as_secs() % 1000) as u64, d.subsec_micros()),
-        Err(_) => (0, 0),
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        LayoutConfig {
+            top: TOP_ROW.to_vec(),
+            top_widths: None,
+            dense: false,
+        }
     }
 }
 
-// ---------------- Runtime wiring ----------------
+/// Exporter sanity checks for the diagnostics popup (`d`).
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct DiagnosticsConfig {
+    /// Flag TAS that differs from Mach × ISA speed of sound by more than this fraction.
+    /// Unset = off.
+    tas_mach_tolerance: Option<f64>,
+}
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let (tx, rx) = watch::channel(UiState::default());
-    let port = std::env::var("PORT")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(5010);
-
-    task::spawn(udp_listener(
-        format!("127.0.0.1:{port}"),
-        tx.clone(),
-        rx.clone(),
-    ));
-    // Optional Wacom: start movement logic only if a device is available right now.
-    #[cfg(feature = "wacom")]
-    {
-        if let Some((path, dev)) = try_open_wacom_pad_now() {
-            eprintln!("Using Wacom pad at {}", path);
-            task::spawn(wacom_listener_with_device(
-                tx.clone(),
-                rx.clone(),
-                path,
-                dev,
-            ));
-        } else {
-            eprintln!(
-                "No Wacom pad found (or no permission). Running dashboard without pad controls."
-            );
+/// Pad settings; accepted (and ignored) by builds without the wacom feature.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct WacomConfig {
+    /// ABS_MISC values this close to 0 keep the last inferred pad side.
+    #[cfg_attr(not(feature = "wacom"), allow(dead_code))]
+    abs_deadzone: i32,
+    /// Button code (as a string key, e.g. "256") -> action, over the built-in mapping.
+    #[cfg_attr(not(feature = "wacom"), allow(dead_code))]
+    buttons: BTreeMap<String, PadAction>,
+}
+
+/// Settings for one airframe; anything set here replaces the global value.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct Airframe {
+    limits: Limits,
+    /// Show AoA in this airframe's indexer units instead of degrees.
+    aoa_units: Option<AoaUnits>,
+    /// Pane to focus when this airframe appears; unset = `home`.
+    focus: Option<Pane>,
+    /// Also show that pane fullscreen.
+    fullscreen: bool,
+}
+
+/// Linear degrees-to-units conversion with an on-speed bracket, in units.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AoaUnits {
+    #[serde(default = "default_per_deg")]
+    per_deg: f64,
+    #[serde(default)]
+    offset: f64,
+    /// [low, high]: below is fast, above is slow.
+    on_speed: [f64; 2],
+}
+
+fn default_per_deg() -> f64 {
+    1.0
+}
+
+impl AoaUnits {
+    fn units(&self, aoa_rad: f64) -> f64 {
+        aoa_rad.to_degrees() * self.per_deg + self.offset
+    }
+}
+
+/// Side effects of warnings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Alerts {
+    /// Ring the terminal bell (at most once a second) while over the G limit.
+    bell: bool,
+    /// Show a PULL UP banner with the ground-proximity warning, not just a red AGL.
+    pull_up: bool,
+    /// Fullscreen the pane behind a new warning; the view comes back when it clears.
+    focus: bool,
+}
+
+impl Default for Alerts {
+    fn default() -> Self {
+        Alerts {
+            bell: false,
+            pull_up: true,
+            focus: false,
         }
     }
+}
 
-    run_tui(rx).await
+/// Low-pass time constants for displayed values, in milliseconds; 0 shows raw values.
+/// Warnings, charts and the recorder always use raw data.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Smoothing {
+    g_ms: f64,
+    aoa_ms: f64,
 }
 
-#[cfg(feature = "wacom")]
-async fn wacom_listener_with_device(
-    tx: watch::Sender<UiState>,
-    rx: watch::Receiver<UiState>,
-    path: String,
-    mut dev: Device,
-) {
-    // For logging context
-    let mut last_side_hint = Side::Left;
-    let mut last_abs_misc: i32 = 0;
-    let mut last_abs_at = Instant::now() - Duration::from_millis(SIDE_TIMEOUT_MS as u64 + 1);
+impl Default for Smoothing {
+    fn default() -> Self {
+        Smoothing {
+            g_ms: 150.0,
+            aoa_ms: 0.0,
+        }
+    }
+}
 
-    loop {
-        match dev.fetch_events() {
-            Ok(iter) => {
-                let mut saw = false;
-                for ev in iter {
-                    saw = true;
+/// Slower redraws while no telemetry arrives, to save power between sorties.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct IdleConfig {
+    /// Seconds without a packet before backing off; 0 disables.
+    after_secs: f64,
+    redraw_ms: u64,
+}
 
-                    if ev.event_type() == EventType::ABSOLUTE {
-                        if let Some(s) = side_from_abs(ev.code(), ev.value()) {
-                            last_side_hint = s;
-                            last_abs_at = Instant::now();
-                        }
-                        if ev.code() == 40 {
-                            last_abs_misc = ev.value();
-                        }
-                    }
+impl Default for IdleConfig {
+    fn default() -> Self {
+        IdleConfig {
+            after_secs: 3.0,
+            redraw_ms: 1000,
+        }
+    }
+}
 
-                    if ev.event_type() == EventType::KEY && ev.value() == 1 {
-                        let code_u16 = ev.code();
-                        let act = map_btn_code(code_u16);
+/// Redraw when the state changes instead of on every tick.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RedrawConfig {
+    on_change: bool,
+    /// Minimum ms between change-driven redraws.
+    min_ms: u64,
+}
 
-                        let mut state = rx.borrow().clone();
-                        match act {
-                            PadAction::Select => {
-                                if state.fullscreen == Some(state.focused) {
-                                    state.fullscreen = None;
-                                } else {
-                                    state.fullscreen = Some(state.focused);
-                                }
-                            }
-                            PadAction::Up
-                            | PadAction::Down
-                            | PadAction::Left
-                            | PadAction::Right => {
-                                state.focused = move_focus(state.focused, act);
-                            }
-                            PadAction::Unknown => {}
+impl Default for RedrawConfig {
+    fn default() -> Self {
+        RedrawConfig {
+            on_change: false,
+            min_ms: 50,
+        }
+    }
+}
+
+/// Dim the whole screen after a while without packets or keys, against burn-in on
+/// permanent installations.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ScreensaverConfig {
+    /// Seconds without a packet or key before dimming; 0 disables.
+    after_secs: f64,
+    /// Brightness kept while dimmed, 0 (black) to 1.
+    level: f64,
+}
+
+impl Default for ScreensaverConfig {
+    fn default() -> Self {
+        ScreensaverConfig {
+            after_secs: 0.0,
+            level: 0.3,
+        }
+    }
+}
+
+/// Link health: flag and dim the dashboard once packets stop arriving (a paused game,
+/// a dead exporter), so the last frame isn't mistaken for live data.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StaleConfig {
+    /// Seconds since the last packet before NO DATA shows; 0 disables.
+    after_secs: f64,
+    /// Brightness kept for the panes meanwhile, 0 (black) to 1.
+    level: f64,
+}
+
+impl Default for StaleConfig {
+    fn default() -> Self {
+        StaleConfig {
+            after_secs: 2.0,
+            level: 0.5,
+        }
+    }
+}
+
+/// Startup overlay with the bound inputs and devices found.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SplashConfig {
+    /// Seconds before it dismisses itself; 0 disables it.
+    secs: f64,
+}
+
+impl Default for SplashConfig {
+    fn default() -> Self {
+        SplashConfig { secs: 2.0 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct HeaderConfig {
+    /// Summarize the last this many seconds of IAS and altitude in the header. Unset = off.
+    stats_secs: Option<f64>,
+    /// Header at startup; `H` cycles.
+    mode: HeaderMode,
+}
+
+/// Keep the view (units, night mode, focus, hidden panes, ...) across restarts.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct StateConfig {
+    persist: bool,
+    /// Unset = state.json beside the config file.
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum HeaderMode {
+    /// Bordered, with position, receive details and the exit hint.
+    #[default]
+    Detailed,
+    /// One borderless line: airframe and packet counts.
+    Compact,
+    /// No header; a borderless line still appears for warnings and messages.
+    Hidden,
+}
+
+impl HeaderMode {
+    fn next(self) -> HeaderMode {
+        match self {
+            HeaderMode::Detailed => HeaderMode::Compact,
+            HeaderMode::Compact => HeaderMode::Hidden,
+            HeaderMode::Hidden => HeaderMode::Detailed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum Units {
+    /// Meters, knots and m/s, as most DCS cockpits outside the US read.
+    #[default]
+    Mixed,
+    /// Meters, km/h and m/s.
+    Metric,
+    /// Feet, knots and ft/min.
+    Imperial,
+    /// Feet and meters side by side, knots with km/h; charts and tapes use feet and knots.
+    Both,
+}
+
+const M_TO_FT: f64 = 3.280_84;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum AngleUnit {
+    #[default]
+    Degrees,
+    /// NATO mils, 6400 per circle.
+    Mils,
+}
+
+impl AngleUnit {
+    fn toggle(self) -> Self {
+        match self {
+            AngleUnit::Degrees => AngleUnit::Mils,
+            AngleUnit::Mils => AngleUnit::Degrees,
+        }
+    }
+
+    /// Fixed-width value with its unit, e.g. "  2.86°" or "   51 mil".
+    fn fmt(self, rad: f64) -> String {
+        match self {
+            AngleUnit::Degrees => format!("{:>6.2}°", rad.to_degrees()),
+            AngleUnit::Mils => format!("{:>5.0} mil", rad * 3200.0 / std::f64::consts::PI),
+        }
+    }
+}
+
+impl Units {
+    /// `u`: metric, imperial, both, and round again.
+    fn next(self) -> Self {
+        match self {
+            Units::Mixed => Units::Metric,
+            Units::Metric => Units::Imperial,
+            Units::Imperial => Units::Both,
+            Units::Both => Units::Mixed,
+        }
+    }
+
+    fn speed_unit(self) -> &'static str {
+        match self {
+            Units::Metric => "km/h",
+            Units::Mixed | Units::Imperial | Units::Both => "kt",
+        }
+    }
+
+    /// m/s to `speed_unit`.
+    fn speed_scale(self) -> f64 {
+        match self {
+            Units::Metric => 3.6,
+            Units::Mixed | Units::Imperial | Units::Both => 1.943_844,
+        }
+    }
+
+    fn alt_unit(self) -> &'static str {
+        match self {
+            Units::Mixed | Units::Metric => "m",
+            Units::Imperial | Units::Both => "ft",
+        }
+    }
+
+    fn alt_scale(self) -> f64 {
+        match self {
+            Units::Mixed | Units::Metric => 1.0,
+            Units::Imperial | Units::Both => M_TO_FT,
+        }
+    }
+}
+
+/// Colors resolved for the renderer from FOCUS_COLOR and night mode; the rest of the
+/// palette (focus style, chart colors, G gradient) is read from `[colors]` directly.
+#[derive(Debug, Clone)]
+struct Theme {
+    focus: Color,
+    focus_mod: Modifier,
+    /// Painted under everything; widgets without their own colors inherit it.
+    base: Style,
+    /// Recolor every foreground to `NIGHT_FG` so nothing bright reaches the screen.
+    night: bool,
+}
+
+const NIGHT_FG: Color = Color::Red;
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            focus: Color::Yellow,
+            focus_mod: Modifier::empty(),
+            base: Style::new(),
+            night: false,
+        }
+    }
+}
+
+impl Theme {
+    /// Dim red on black to preserve night vision. Everything is red, so focus is bold.
+    fn night() -> Self {
+        Theme {
+            focus: NIGHT_FG,
+            focus_mod: Modifier::BOLD,
+            base: Style::new().fg(NIGHT_FG).bg(Color::Black),
+            night: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ChartsConfig {
+    /// Advance charts once per this many ms, averaging the packets in between, so
+    /// scroll speed doesn't depend on the exporter's rate. 0 = one sample per packet.
+    sample_ms: u64,
+    /// Show this many seconds of history regardless of pane width, reduced to fit with
+    /// min/max-preserving downsampling. Unset = one sample per column.
+    span_secs: Option<f64>,
+    /// Chart given twice the height of the others. Unset = equal split.
+    primary: Option<ChartName>,
+    ias: ChartConfig,
+    alt: ChartConfig,
+    mach: ChartConfig,
+    ps: ChartConfig,
+    vv: ChartConfig,
+    g: ChartConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ChartConfig {
+    style: ChartStyle,
+    /// Fill from the top (bars show distance below the window maximum).
+    invert: bool,
+    /// Fixed [min, max] in the chart's display unit; values outside clamp to the edge.
+    /// Unset = scale to the visible data.
+    range: Option<[f64; 2]>,
+    /// Changes smaller than this (display unit) repeat the previous sample. 0 = off.
+    deadband: f64,
+    /// Moving average over this many stored samples, applied when drawing. 1 = raw.
+    smooth_window: usize,
+}
+
+impl Default for ChartConfig {
+    fn default() -> Self {
+        ChartConfig {
+            style: ChartStyle::default(),
+            invert: false,
+            range: None,
+            deadband: 0.0,
+            smooth_window: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ChartName {
+    Ias,
+    Alt,
+    Mach,
+    Ps,
+    Vv,
+    G,
+}
+
+impl ChartName {
+    fn pane(self) -> Pane {
+        match self {
+            ChartName::Ias => Pane::IasChart,
+            ChartName::Alt => Pane::AltChart,
+            ChartName::Mach => Pane::MachChart,
+            ChartName::Ps => Pane::PsChart,
+            ChartName::Vv => Pane::VvChart,
+            ChartName::G => Pane::GChart,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum ChartStyle {
+    /// One bar per column; works on any terminal/font.
+    #[default]
+    Sparkline,
+    /// Braille dot line plot: 2x horizontal, 4x vertical resolution per cell.
+    Braille,
+}
+
+impl Config {
+    /// Chart samples to keep per chart.
+    fn history(&self) -> usize {
+        self.history.unwrap_or(HISTORY)
+    }
+
+    /// All styling goes through here so `--no-color` / NO_COLOR can strip it.
+    fn style(&self, style: Style) -> Style {
+        if self.no_color {
+            Style::default()
+        } else if self.theme.night {
+            // Keep black-on-red alerts legible; any other color collapses to red.
+            Style {
+                fg: style
+                    .fg
+                    .map(|c| if c == Color::Black { c } else { NIGHT_FG }),
+                bg: style
+                    .bg
+                    .map(|c| if c == Color::Black { c } else { NIGHT_FG }),
+                ..style
+            }
+        } else {
+            style
+        }
+    }
+
+    /// Optional configured foreground, through `style` so night/no-color still apply.
+    fn fg(&self, color: Option<ConfColor>) -> Style {
+        match color {
+            Some(ConfColor(c)) => self.style(Style::default().fg(c)),
+            None => Style::default(),
+        }
+    }
+
+    fn focus_style(&self, focused: bool) -> Style {
+        if focused {
+            self.style(
+                Style::default()
+                    .fg(self.theme.focus)
+                    .add_modifier(self.theme.focus_mod),
+            )
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Bordered pane block, marked per colors.focus_style when focused. A tint would
+    /// wash the pane red at night, so night mode uses the border instead.
+    fn pane_block<T: Into<Line<'static>>>(&self, title: T, focused: bool) -> Block<'static> {
+        let block = Block::default().borders(Borders::ALL).title(title);
+        if !focused {
+            return block;
+        }
+        match self.colors.focus_style {
+            FocusStyle::Title => block.title_style(
+                self.style(
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(self.theme.focus)
+                        .add_modifier(self.theme.focus_mod),
+                ),
+            ),
+            FocusStyle::Tint if !self.theme.night => {
+                block.style(self.style(Style::default().bg(self.colors.focus_tint.0)))
+            }
+            _ => block.border_style(self.focus_style(true)),
+        }
+    }
+
+    /// Semantic checks serde can't express; each entry names the offending key.
+    fn problems(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        let mut check = |ok: bool, key: &str, want: &str| {
+            if !ok {
+                out.push(format!("{key}: must be {want}"));
+            }
+        };
+        let stops = &self.colors.g_gradient;
+        check(
+            !stops.is_empty()
+                && stops.iter().all(|(x, _)| x.is_finite())
+                && stops.windows(2).all(|w| w[1].0 > w[0].0),
+            "colors.g_gradient",
+            "[position, color] stops with positions rising",
+        );
+        for (name, a) in &self.airframes {
+            if let Some(u) = &a.aoa_units {
+                let [lo, hi] = u.on_speed;
+                let at = format!("airframes.\"{name}\".aoa_units");
+                check(
+                    u.per_deg.is_finite() && u.per_deg != 0.0,
+                    &format!("{at}.per_deg"),
+                    "a non-zero number",
+                );
+                check(u.offset.is_finite(), &format!("{at}.offset"), "a number");
+                check(
+                    lo.is_finite() && hi.is_finite() && lo < hi,
+                    &format!("{at}.on_speed"),
+                    "[low, high] with low < high",
+                );
+            }
+        }
+        let mut limits = vec![("limits".to_string(), &self.limits)];
+        limits.extend(
+            self.airframes
+                .iter()
+                .map(|(name, a)| (format!("airframes.\"{name}\".limits"), &a.limits)),
+        );
+        for (at, l) in limits {
+            for (v, key) in [
+                (l.low_fuel_kg, "low_fuel_kg"),
+                (l.min_agl_m, "min_agl_m"),
+                (l.rpm_split, "rpm_split"),
+                (l.temp_split, "temp_split"),
+            ] {
+                if let Some(v) = v {
+                    check(
+                        v.is_finite() && v >= 0.0,
+                        &format!("{at}.{key}"),
+                        "a number >= 0",
+                    );
+                }
+            }
+            for (v, key) in [(l.g_max, "g_max"), (l.alt_rate_max, "alt_rate_max")] {
+                if let Some(v) = v {
+                    check(
+                        v.is_finite() && v > 0.0,
+                        &format!("{at}.{key}"),
+                        "a number > 0",
+                    );
+                }
+            }
+            if let Some(n) = l.alt_rate_samples {
+                check(n >= 1, &format!("{at}.alt_rate_samples"), ">= 1");
+            }
+            if let Some(pts) = &l.envelope {
+                check(
+                    !pts.is_empty()
+                        && pts
+                            .iter()
+                            .all(|[a, g]| a.is_finite() && g.is_finite() && *g > 0.0)
+                        && pts.windows(2).all(|w| w[1][0] > w[0][0]),
+                    &format!("{at}.envelope"),
+                    "[aoa_deg, g] pairs with g > 0 and AoA strictly rising",
+                );
+            }
+        }
+        let idle = &self.idle;
+        check(
+            idle.after_secs.is_finite() && idle.after_secs >= 0.0,
+            "idle.after_secs",
+            "a number >= 0",
+        );
+        check(idle.redraw_ms > 0, "idle.redraw_ms", "> 0");
+        let saver = &self.screensaver;
+        check(
+            saver.after_secs.is_finite() && saver.after_secs >= 0.0,
+            "screensaver.after_secs",
+            "a number >= 0",
+        );
+        check(
+            (0.0..=1.0).contains(&saver.level),
+            "screensaver.level",
+            "between 0 and 1",
+        );
+        let stale = &self.stale;
+        check(
+            stale.after_secs.is_finite() && stale.after_secs >= 0.0,
+            "stale.after_secs",
+            "a number >= 0",
+        );
+        check(
+            (0.0..=1.0).contains(&stale.level),
+            "stale.level",
+            "between 0 and 1",
+        );
+        check(
+            self.splash.secs.is_finite() && self.splash.secs >= 0.0,
+            "splash.secs",
+            "a number >= 0",
+        );
+        check(self.wacom.abs_deadzone >= 0, "wacom.abs_deadzone", ">= 0");
+        for code in self.wacom.buttons.keys() {
+            check(
+                code.parse::<u16>().is_ok(),
+                &format!("wacom.buttons.\"{code}\""),
+                "keyed by a button code (0-65535)",
+            );
+        }
+        let top = &self.layout.top;
+        check(
+            top.iter().all(|p| TOP_PANES.contains(p))
+                && top.iter().collect::<HashSet<_>>().len() == top.len(),
+            "layout.top",
+            "distinct names out of \"flight\", \"att\", \"systems\" and \"computed\"",
+        );
+        if let Some(w) = &self.layout.top_widths {
+            check(
+                w.len() == top.len() && w.iter().all(|&w| w > 0),
+                "layout.top_widths",
+                "one width > 0 per layout.top pane",
+            );
+            check(
+                w.iter().map(|&w| w as u32).sum::<u32>() == 100,
+                "layout.top_widths",
+                "percentages summing to 100",
+            );
+        }
+        if let Some(v) = self.diagnostics.tas_mach_tolerance {
+            check(
+                v.is_finite() && v > 0.0,
+                "diagnostics.tas_mach_tolerance",
+                "a number > 0",
+            );
+        }
+        if let Some(v) = self.charts.span_secs {
+            check(v.is_finite() && v > 0.0, "charts.span_secs", "a number > 0");
+        }
+        if let Some(v) = self.header.stats_secs {
+            check(
+                v.is_finite() && v > 0.0,
+                "header.stats_secs",
+                "a number > 0",
+            );
+        }
+        for (c, key) in [
+            (&self.charts.ias, "charts.ias"),
+            (&self.charts.alt, "charts.alt"),
+            (&self.charts.mach, "charts.mach"),
+            (&self.charts.ps, "charts.ps"),
+        ] {
+            if let Some([lo, hi]) = c.range {
+                let ok = lo.is_finite() && hi.is_finite() && lo < hi;
+                check(ok, &format!("{key}.range"), "[min, max] with min < max");
+            }
+            let ok = c.deadband.is_finite() && c.deadband >= 0.0;
+            check(ok, &format!("{key}.deadband"), "a number >= 0");
+            check(
+                (1..=HISTORY).contains(&c.smooth_window),
+                &format!("{key}.smooth_window"),
+                &format!("between 1 and {HISTORY}"),
+            );
+        }
+        for (v, key) in [
+            (self.smoothing.g_ms, "smoothing.g_ms"),
+            (self.smoothing.aoa_ms, "smoothing.aoa_ms"),
+        ] {
+            check(v.is_finite() && v >= 0.0, key, "a number >= 0");
+        }
+        for (name, c) in &self.computed {
+            if let Err(e) = &c.0 {
+                check(
+                    false,
+                    &format!("computed.{name}"),
+                    &format!("an expression ({e})"),
+                );
+            }
+        }
+        out
+    }
+
+    fn aoa_units_for(&self, airframe: Option<&str>) -> Option<AoaUnits> {
+        airframe.and_then(|n| self.airframes.get(n)?.aoa_units)
+    }
+
+    /// Global limits with the airframe's overrides applied.
+    fn limits_for(&self, airframe: Option<&str>) -> Limits {
+        match airframe.and_then(|n| self.airframes.get(n)) {
+            Some(a) => self.limits.overlaid(&a.limits),
+            None => self.limits.clone(),
+        }
+    }
+
+    fn chart(&self, pane: Pane) -> &ChartConfig {
+        match pane {
+            Pane::AltChart => &self.charts.alt,
+            Pane::MachChart => &self.charts.mach,
+            Pane::PsChart => &self.charts.ps,
+            Pane::VvChart => &self.charts.vv,
+            Pane::GChart => &self.charts.g,
+            _ => &self.charts.ias,
+        }
+    }
+}
+
+/// DCSCTL_CONFIG wins; otherwise $XDG_CONFIG_HOME/dcsctl/config.toml (or ~/.config/...).
+fn config_path() -> Option<PathBuf> {
+    if let Ok(p) = std::env::var("DCSCTL_CONFIG") {
+        return Some(PathBuf::from(p));
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(base.join("dcsctl").join("config.toml"))
+}
+
+/// A missing file is fine (defaults); a malformed or out-of-range one is an error.
+fn load_config() -> Result<Config> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    let cfg: Config = match fs::read_to_string(&path) {
+        Ok(text) => {
+            toml::from_str(&text).with_context(|| format!("invalid config {}", path.display()))?
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("cannot read config {}", path.display()));
+        }
+    };
+    let problems = cfg.problems();
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "invalid config {}:\n  {}",
+            path.display(),
+            problems.join("\n  ")
+        );
+    }
+    Ok(cfg)
+}
+
+/// Every option with its default, commented. Must stay loadable as-is.
+const CONFIG_TEMPLATE: &str = include_str!("../config.example.toml");
+
+/// `--init-config`: write the template where `load_config` will look for it.
+fn init_config(force: bool) -> Result<()> {
+    let path = config_path().context("no config location (set HOME or DCSCTL_CONFIG)")?;
+    if path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        );
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+    fs::write(&path, CONFIG_TEMPLATE)
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+/// `--check-config`: report what would be loaded, or every error, without starting.
+fn check_config() -> Result<()> {
+    let mut errors = Vec::new();
+    match config_path() {
+        Some(path) if path.exists() => match load_config() {
+            Ok(_) => println!("{}: ok", path.display()),
+            Err(e) => errors.push(format!("{e:#}")),
+        },
+        Some(path) => println!("{}: not found, using defaults", path.display()),
+        None => println!("no config location (HOME unset), using defaults"),
+    }
+    if let Ok(v) = std::env::var("FOCUS_COLOR")
+        && v.parse::<Color>().is_err()
+    {
+        errors.push(format!("FOCUS_COLOR={v} is not a color name or #rrggbb"));
+    }
+    if errors.is_empty() {
+        return Ok(());
+    }
+    for e in &errors {
+        eprintln!("{e}");
+    }
+    anyhow::bail!("{} config error(s)", errors.len())
+}
+
+// ---------------- UI state ----------------
+
+/// Config names: "flight", "att", "systems", "computed", "stats", "ias", "alt", "mach",
+/// "ps", "vv", "g".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)] // Inputs is only reachable from from_index for now
+enum Pane {
+    #[default]
+    Flight = 0,
+    Att = 1,
+    Systems = 2,
+    #[serde(skip)]
+    Inputs = 3,
+    #[serde(rename = "ias")]
+    IasChart = 4,
+    #[serde(rename = "alt")]
+    AltChart = 5,
+    #[serde(rename = "mach")]
+    MachChart = 6,
+    /// Values from the [computed] expressions.
+    Computed = 7,
+    /// Specific excess power, the rate of change of energy height.
+    #[serde(rename = "ps")]
+    PsChart = 8,
+    /// Packet rates and parse errors, for exporter debugging.
+    Stats = 9,
+    /// Vertical velocity, climb above the zero line and descent below.
+    #[serde(rename = "vv")]
+    VvChart = 10,
+    /// Load factor, negative G included, red above limits.g_max.
+    #[serde(rename = "g")]
+    GChart = 11,
+}
+const PANE_COUNT: usize = 12;
+
+/// Default top row, left to right.
+const TOP_ROW: [Pane; 3] = [Pane::Flight, Pane::Att, Pane::Systems];
+/// Panes layout.top may place in the top row.
+const TOP_PANES: [Pane; 5] = [
+    Pane::Flight,
+    Pane::Att,
+    Pane::Systems,
+    Pane::Computed,
+    Pane::Stats,
+];
+const CHART_COUNT: usize = 6;
+const CHART_ROWS: [Pane; CHART_COUNT] = [
+    Pane::IasChart,
+    Pane::AltChart,
+    Pane::MachChart,
+    Pane::PsChart,
+    Pane::VvChart,
+    Pane::GChart,
+];
+/// On-screen order; Shift+1..9 toggles these.
+const LAYOUT_PANES: [Pane; 9] = [
+    Pane::Flight,
+    Pane::Att,
+    Pane::Systems,
+    Pane::IasChart,
+    Pane::AltChart,
+    Pane::MachChart,
+    Pane::PsChart,
+    Pane::VvChart,
+    Pane::GChart,
+];
+
+#[allow(dead_code)]
+impl Pane {
+    fn from_index(i: usize) -> Pane {
+        match i {
+            0 => Pane::Flight,
+            1 => Pane::Att,
+            2 => Pane::Systems,
+            3 => Pane::Inputs,
+            4 => Pane::IasChart,
+            5 => Pane::AltChart,
+            6 => Pane::MachChart,
+            7 => Pane::Computed,
+            8 => Pane::PsChart,
+            9 => Pane::Stats,
+            10 => Pane::VvChart,
+            _ => Pane::GChart,
+        }
+    }
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Chart panes: title, and the factor and unit taking stored samples (m/s, m, Mach,
+    /// m/s, m/s, G) to what is drawn. Ranges and deadbands are in this unit too.
+    fn chart_spec(self, units: Units) -> Option<ChartSpec> {
+        let alt = units.alt_scale();
+        let (title, scale, unit) = match self {
+            Pane::IasChart => ("IAS", units.speed_scale(), units.speed_unit()),
+            Pane::AltChart => ("Altitude MSL", alt, units.alt_unit()),
+            Pane::MachChart => ("Mach", 1.0, ""),
+            Pane::PsChart => match units {
+                Units::Mixed | Units::Metric => ("Ps", alt, "m/s"),
+                Units::Imperial | Units::Both => ("Ps", alt, "ft/s"),
+            },
+            Pane::VvChart => match units {
+                Units::Mixed | Units::Metric => ("VV", 1.0, "m/s"),
+                Units::Imperial | Units::Both => ("VV", M_TO_FT * 60.0, "fpm"),
+            },
+            Pane::GChart => ("G", 1.0, "G"),
+            _ => return None,
+        };
+        let centered = self == Pane::VvChart;
+        Some(ChartSpec {
+            title,
+            scale,
+            unit,
+            centered,
+        })
+    }
+}
+
+/// How a chart pane presents its samples; see `Pane::chart_spec`.
+struct ChartSpec {
+    title: &'static str,
+    scale: f64,
+    unit: &'static str,
+    /// Zero stays mid-height whatever the data, and the border shows the visible range.
+    centered: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct UiState {
+    last: Telemetry,
+    ias_hist: VecDeque<f64>,
+    alt_hist: VecDeque<f64>,
+    mach_hist: VecDeque<f64>,
+    /// Specific excess power, m/s.
+    ps_hist: VecDeque<f64>,
+    /// Vertical velocity, m/s.
+    vv_hist: VecDeque<f64>,
+    /// Load factor (accel.y).
+    g_hist: VecDeque<f64>,
+    /// Lowest and highest G since the airframe last changed, for the G chart title.
+    g_session: Option<[f64; 2]>,
+    /// When each chart sample was taken (shared by the chart histories).
+    hist_times: VecDeque<Instant>,
+    input_log: VecDeque<String>,
+    /// Pad button codes seen this session: presses and the action each maps to.
+    pad_codes: HashMap<u16, (u64, PadAction)>,
+    /// Pad ABS axes seen this session, by code, for tuning side detection.
+    pad_abs: BTreeMap<u16, AbsAxis>,
+    focused: Pane,
+    fullscreen: Option<Pane>,
+    units: Units,
+    warnings: Warnings,
+    seen: Seen,
+    /// Every field received so far, merged; `last` is this deserialized.
+    raw: Value,
+    /// Frozen copies of the state for pinned panes; everything else stays live.
+    pinned: HashMap<Pane, UiState>,
+    /// Rendered cells of panes on a slower refresh_ms, reused until they are due; set
+    /// on the frame being drawn only.
+    held: HashMap<Pane, Arc<Mutex<HeldPane>>>,
+    night: bool,
+    /// Panes removed from the layout; the rest expand into their space.
+    hidden: HashSet<Pane>,
+    /// Top-row panes in order, from layout.top.
+    top_row: Vec<Pane>,
+    /// Index into CHART_ROWS of the one chart shown in a single slot; None stacks them all.
+    chart_selection: Option<usize>,
+    rx_stats: RxStats,
+    /// Frames written by --record so far, for the header's REC mark.
+    recorded: Option<RecStatus>,
+    /// Position and controls of --replay.
+    replay: Option<ReplayStatus>,
+    /// While replaying, the recording time of the latest frame (as run start + `t_ms`).
+    /// Chart samples are stamped with it and chart windows end at it.
+    replay_clock: Option<Instant>,
+    /// Gaps in the exporter's `seq` numbers.
+    seq: SeqStats,
+    /// Input and peer of the last packet, e.g. "127.0.0.1:5010 ← 10.0.0.2:6123".
+    rx_from: Option<String>,
+    /// Recent (time, flight path angle) samples for the horizon's trend vector.
+    fpa_hist: VecDeque<(Instant, f64)>,
+    angles: AngleUnit,
+    /// Show the trend vector on the horizon.
+    trend: bool,
+    /// AoA in the airframe's units (airframes.*.aoa_units) rather than `angles`; `A`.
+    aoa_units: bool,
+    /// Display-only filtered copies of noisy values.
+    smooth_g: ExpFilter,
+    smooth_aoa: ExpFilter,
+    gs: GroundSpeed,
+    ps: ExcessPower,
+    /// Last altitude sample and when it arrived, for ALT RATE.
+    alt_prev: Option<(Instant, f64)>,
+    extremes: Extremes,
+    /// Show the focused pane's session min/max in its title; `x`.
+    show_extremes: bool,
+    /// Packets since the last chart sample, when charts.sample_ms is set.
+    chart_acc: ChartAcc,
+    /// Short-lived header message and when it was set.
+    flash: Option<(Instant, String)>,
+    flight_log: FlightLog,
+    max_split: MaxSplit,
+    /// MSL altitude zeroed with `z`; the Flight pane then also shows height above it.
+    alt_datum: Option<f64>,
+    /// Flight log popup is open.
+    show_log: bool,
+    /// Diagnostics popup is open.
+    show_diag: bool,
+    /// Startup overlay lines and when it opened; any key closes it.
+    splash: Option<(Instant, Vec<String>)>,
+    /// Screensaver is on: the frame is drawn dimmed until a packet or key arrives.
+    dimmed: bool,
+    /// Dense numbers-only layout instead of the panes; `D`.
+    dense: bool,
+    header: HeaderMode,
+    /// Tracks of other airframes streaming at the same time, by name; `m` cycles.
+    airframes: BTreeMap<String, UiState>,
+    /// Focus or layout changed by hand since the airframe last changed; its profile
+    /// then leaves them alone.
+    manual_view: bool,
+    /// Focus and fullscreen came from an airframe profile, to undo for one without.
+    profile_view: bool,
+    /// Pane alerts.focus put fullscreen, and the focus and fullscreen it replaced.
+    alert_view: Option<(Pane, Pane, Option<Pane>)>,
+    /// --compare: the second recording, charted over this one.
+    compare: Option<Box<Compare>>,
+}
+
+/// The B side of --compare.
+#[derive(Debug, Clone)]
+struct Compare {
+    /// File stems of A and B, for the chart legend.
+    names: [String; 2],
+    track: UiState,
+}
+
+/// Latest raw value of one pad ABS axis and the range seen.
+#[derive(Debug, Clone)]
+struct AbsAxis {
+    value: i32,
+    min: i32,
+    max: i32,
+    events: u64,
+}
+
+/// Input counters for diagnosing flaky exporters; `c` resets them.
+#[derive(Debug, Clone, Default)]
+struct RxStats {
+    /// Reads handed to `ingest` (a datagram, or a line from a stream input).
+    datagrams: u64,
+    /// JSON lines accepted.
+    ok: u64,
+    /// Lines that weren't valid JSON telemetry, or undecodable datagrams.
+    err: u64,
+    bytes: u64,
+    /// (when, bytes, frames) of each read within RX_RATE_WINDOW, for the rates.
+    recent: VecDeque<(Instant, usize, usize)>,
+    last_error: Option<RxError>,
+}
+
+/// The most recent frame `ingest` turned away.
+#[derive(Debug, Clone)]
+struct RxError {
+    wall: SystemTime,
+    reason: String,
+    /// The start of the offending frame.
+    excerpt: String,
+}
+
+impl RxStats {
+    fn note(&mut self, bytes: usize, ok: usize, rejected: u64, error: Option<RxError>) {
+        let now = Instant::now();
+        self.datagrams += 1;
+        self.ok += ok as u64;
+        self.err += rejected;
+        self.bytes += bytes as u64;
+        self.recent.push_back((now, bytes, ok));
+        while self
+            .recent
+            .front()
+            .is_some_and(|(at, ..)| now.duration_since(*at) > RX_RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+        if error.is_some() {
+            self.last_error = error;
+        }
+    }
+
+    /// Frames and bytes per second over the last RX_RATE_WINDOW.
+    fn rates(&self) -> (f64, f64) {
+        let (bytes, frames) = self
+            .recent
+            .iter()
+            .filter(|(at, ..)| at.elapsed() <= RX_RATE_WINDOW)
+            .fold((0, 0), |(b, n), (_, bytes, frames)| (b + bytes, n + frames));
+        let secs = RX_RATE_WINDOW.as_secs_f64();
+        (frames as f64 / secs, bytes as f64 / secs)
+    }
+}
+
+/// Frame loss from the exporter's `seq` counter. A lower number close behind the last
+/// one is a late or duplicate frame; one just past a u16/u32/u64 rollover is a wrap, and
+/// anything else lower, or implausibly far ahead, means the exporter restarted.
+#[derive(Debug, Clone, Default)]
+struct SeqStats {
+    last: Option<u64>,
+    /// Frames carrying a seq.
+    frames: u64,
+    dropped: u64,
+    late: u64,
+    restarts: u64,
+}
+
+impl SeqStats {
+    fn update(&mut self, seq: u64) {
+        self.frames = self.frames.saturating_add(1);
+        if let Some(last) = self.last {
+            let wrap = [u16::MAX as u64, u32::MAX as u64, u64::MAX]
+                .into_iter()
+                .find(|&max| last <= max && max - last < SEQ_WINDOW && seq < SEQ_WINDOW);
+            if seq > last && seq - last <= SEQ_MAX_GAP {
+                self.dropped = self.dropped.saturating_add(seq - last - 1);
+            } else if let Some(max) = wrap {
+                self.dropped = self.dropped.saturating_add(max - last + seq);
+            } else if seq > last {
+                self.restarts = self.restarts.saturating_add(1);
+            } else if last - seq < SEQ_WINDOW {
+                // Keep counting from the newest frame.
+                self.late = self.late.saturating_add(1);
+                return;
+            } else {
+                self.restarts = self.restarts.saturating_add(1);
+            }
+        }
+        self.last = Some(seq);
+    }
+}
+
+/// Running sum of [ias, alt, mach, ps] between chart ticks.
+#[derive(Debug, Clone, Default)]
+struct ChartAcc {
+    sum: [f64; CHART_COUNT],
+    n: u32,
+}
+
+impl ChartAcc {
+    fn add(&mut self, v: [f64; CHART_COUNT]) {
+        for (s, v) in self.sum.iter_mut().zip(v) {
+            *s += v;
+        }
+        self.n += 1;
+    }
+
+    /// Mean of the accumulated packets, resetting for the next interval.
+    fn take(&mut self) -> Option<[f64; CHART_COUNT]> {
+        let acc = std::mem::take(self);
+        (acc.n > 0).then(|| acc.sum.map(|s| s / acc.n as f64))
+    }
+}
+
+// Positions closer together in time than this are too noisy to difference.
+const GS_MIN_DT: Duration = Duration::from_millis(500);
+const GS_TAU: Duration = Duration::from_secs(1);
+// Below this ground speed (m/s, ~20 kt: hover, taxi) the Flight pane shows no gradient.
+const FPA_MIN_GS: f64 = 10.0;
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Ground speed derived from successive lat/lon fixes (the exporter doesn't send it).
+#[derive(Debug, Clone, Default)]
+struct GroundSpeed {
+    anchor: Option<(Instant, f64, f64)>,
+    filt: ExpFilter,
+}
+
+impl GroundSpeed {
+    fn update(&mut self, lat: f64, lon: f64, now: Instant) {
+        let Some((at, lat0, lon0)) = self.anchor else {
+            self.anchor = Some((now, lat, lon));
+            return;
+        };
+        let dt = now.saturating_duration_since(at);
+        if dt < GS_MIN_DT {
+            return;
+        }
+        let v = haversine_m(lat0, lon0, lat, lon) / dt.as_secs_f64();
+        // A jump (respawn, new mission) is not speed; start over from here.
+        if v < 1500.0 {
+            self.filt.update(v, now, GS_TAU);
+        } else {
+            self.filt = ExpFilter::default();
+        }
+        self.anchor = Some((now, lat, lon));
+    }
+
+    /// m/s, once two fixes far enough apart have arrived.
+    fn get(&self) -> Option<f64> {
+        self.filt.get()
+    }
+}
+
+// Differentiating energy height over shorter steps mostly amplifies noise.
+const PS_MIN_DT: Duration = Duration::from_millis(250);
+const PS_TAU: Duration = Duration::from_secs(1);
+const G0: f64 = 9.806_65;
+
+/// Specific excess power (Ps): the rate of change of energy height, altitude plus
+/// TAS²/2g, in m/s. Positive is gaining energy.
+#[derive(Debug, Clone, Default)]
+struct ExcessPower {
+    anchor: Option<(Instant, f64)>,
+    filt: ExpFilter,
+}
+
+impl ExcessPower {
+    fn update(&mut self, alt_m: f64, tas_ms: f64, now: Instant) {
+        let es = alt_m + tas_ms * tas_ms / (2.0 * G0);
+        let Some((at, es0)) = self.anchor else {
+            self.anchor = Some((now, es));
+            return;
+        };
+        let dt = now.saturating_duration_since(at);
+        if dt < PS_MIN_DT {
+            return;
+        }
+        self.filt.update((es - es0) / dt.as_secs_f64(), now, PS_TAU);
+        self.anchor = Some((now, es));
+    }
+
+    fn get(&self) -> Option<f64> {
+        self.filt.get()
+    }
+}
+
+/// Great-circle distance in meters between two points in degrees.
+fn haversine_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (p1, p2) = (lat1.to_radians(), lat2.to_radians());
+    let dp = p2 - p1;
+    let dl = (lon2 - lon1).to_radians();
+    let a = (dp / 2.0).sin().powi(2) + p1.cos() * p2.cos() * (dl / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * a.sqrt().min(1.0).asin()
+}
+
+/// First-order low-pass filter over irregularly spaced samples. With time constant
+/// `tau`, a step input reaches ~63% after `tau` and ~95% after `3 * tau`.
+#[derive(Debug, Clone, Default)]
+struct ExpFilter {
+    value: Option<f64>,
+    at: Option<Instant>,
+}
+
+impl ExpFilter {
+    fn update(&mut self, x: f64, now: Instant, tau: Duration) -> f64 {
+        let y = match (self.value, self.at) {
+            (Some(prev), Some(at)) if !tau.is_zero() => {
+                let dt = now.saturating_duration_since(at).as_secs_f64();
+                let alpha = 1.0 - (-dt / tau.as_secs_f64()).exp();
+                prev + alpha * (x - prev)
+            }
+            _ => x,
+        };
+        self.value = Some(y);
+        self.at = Some(now);
+        y
+    }
+
+    fn get(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+/// When each logical group of fields last arrived, so a partially failing exporter
+/// shows up on just the panes it affects.
+#[derive(Debug, Clone, Default)]
+struct Seen {
+    /// Any packet at all, whatever it carried.
+    packet: Option<Instant>,
+    flight: Option<Instant>,
+    att: Option<Instant>,
+    engine: Option<Instant>,
+    mech: Option<Instant>,
+}
+
+impl Seen {
+    fn update(&mut self, t: &Telemetry, now: Instant) {
+        self.packet = Some(now);
+        if t.has_flight() {
+            self.flight = Some(now);
+        }
+        if t.att.is_some() || t.accel.is_some() {
+            self.att = Some(now);
+        }
+        if t.engine.is_some() || t.fuel_kg.is_some() {
+            self.engine = Some(now);
+        }
+        if t.mech.is_some() {
+            self.mech = Some(now);
+        }
+    }
+
+    /// True if any group feeding `pane` has gone quiet. Never-seen groups don't count.
+    fn stale(&self, pane: Pane) -> bool {
+        let groups: &[Option<Instant>] = match pane {
+            Pane::Flight
+            | Pane::IasChart
+            | Pane::AltChart
+            | Pane::MachChart
+            | Pane::PsChart
+            | Pane::VvChart => &[self.flight],
+            Pane::Att | Pane::GChart => &[self.att],
+            Pane::Systems => &[self.engine, self.mech],
+            Pane::Inputs | Pane::Computed | Pane::Stats => &[],
+        };
+        groups
+            .iter()
+            .flatten()
+            .any(|at| at.elapsed().as_millis() > FIELD_STALE_MS)
+    }
+}
+
+impl UiState {
+    /// Append [ias, alt, mach, ps, vv, g]. A value within its chart's deadband of the
+    /// previous sample repeats that sample, so the histories stay in step with `hist_times`.
+    fn push_chart_sample(&mut self, sample: [f64; CHART_COUNT], cfg: &Config, at: Instant) {
+        let hists = [
+            &mut self.ias_hist,
+            &mut self.alt_hist,
+            &mut self.mach_hist,
+            &mut self.ps_hist,
+            &mut self.vv_hist,
+            &mut self.g_hist,
+        ];
+        for (i, hist) in hists.into_iter().enumerate() {
+            let pane = CHART_ROWS[i];
+            let scale = pane.chart_spec(self.units).map_or(1.0, |c| c.scale);
+            let band = cfg.chart(pane).deadband / scale;
+            let v = match hist.back() {
+                Some(&held) if (sample[i] - held).abs() < band => held,
+                _ => sample[i],
+            };
+            push_hist(hist, v, cfg.history());
+        }
+        self.hist_times.push_back(at);
+        while self.hist_times.len() > cfg.history() {
+            self.hist_times.pop_front();
+        }
+    }
+
+    /// The time chart windows end at: the wall clock, or the recording's while replaying.
+    fn chart_now(&self) -> Instant {
+        self.replay_clock.unwrap_or_else(Instant::now)
+    }
+
+    /// Projected IAS change in kt over IAS_TREND_LEAD_S, or None without enough recent
+    /// chart history.
+    fn ias_trend(&self) -> Option<f64> {
+        let now = self.chart_now();
+        let n = self
+            .hist_times
+            .iter()
+            .rev()
+            .take_while(|&&t| now.saturating_duration_since(t) <= IAS_TREND_WINDOW)
+            .count();
+        if n < 2 {
+            return None;
+        }
+        let first = self.hist_times.len() - n;
+        let dt = self
+            .hist_times
+            .back()?
+            .saturating_duration_since(self.hist_times[first])
+            .as_secs_f64();
+        if dt < 0.3 {
+            return None;
+        }
+        let (v0, v1) = (*self.ias_hist.get(first)?, *self.ias_hist.back()?);
+        Some((v1 - v0) / dt * IAS_TREND_LEAD_S * 1.943_844)
+    }
+
+    /// Seconds since the last packet, once that passes stale.after_secs. A paused or
+    /// finished replay is quiet on purpose and doesn't count.
+    fn link_stale(&self, cfg: &Config) -> Option<f64> {
+        if self.replay.is_some_and(|r| r.paused || r.finished) {
+            return None;
+        }
+        let age = self.seen.packet?.elapsed().as_secs_f64();
+        (cfg.stale.after_secs > 0.0 && age >= cfg.stale.after_secs).then_some(age)
+    }
+
+    /// Sample history behind a chart pane.
+    fn chart_hist(&self, pane: Pane) -> Option<&VecDeque<f64>> {
+        match pane {
+            Pane::IasChart => Some(&self.ias_hist),
+            Pane::AltChart => Some(&self.alt_hist),
+            Pane::MachChart => Some(&self.mach_hist),
+            Pane::PsChart => Some(&self.ps_hist),
+            Pane::VvChart => Some(&self.vv_hist),
+            Pane::GChart => Some(&self.g_hist),
+            _ => None,
+        }
+    }
+
+    /// Show/hide a pane. Hiding the focused pane moves focus to the first visible one.
+    fn toggle_hidden(&mut self, pane: Pane) {
+        if !self.hidden.remove(&pane) {
+            self.hidden.insert(pane);
+        }
+        let off = self.off_screen();
+        if off.contains(&self.focused)
+            && let Some(&p) = LAYOUT_PANES.iter().find(|p| !off.contains(p))
+        {
+            self.focused = p;
+        }
+    }
+
+    /// Configured top-row panes that aren't hidden, in order.
+    fn top_shown(&self) -> Vec<Pane> {
+        self.top_row
+            .iter()
+            .copied()
+            .filter(|p| !self.hidden.contains(p))
+            .collect()
+    }
+
+    /// Charts not hidden, in layout order.
+    fn charts_shown(&self) -> Vec<Pane> {
+        CHART_ROWS
+            .into_iter()
+            .filter(|p| !self.hidden.contains(p))
+            .collect()
+    }
+
+    /// Charts in the layout: the selected one, or all of them when none is selected
+    /// (or the selected one is hidden).
+    fn chart_slots(&self) -> Vec<Pane> {
+        match self.chart_selection.map(|i| CHART_ROWS[i]) {
+            Some(p) if !self.hidden.contains(&p) => vec![p],
+            _ => self.charts_shown(),
+        }
+    }
+
+    /// Panes not in the layout: hidden ones, top-row panes left out of layout.top and
+    /// charts outside the single slot.
+    fn off_screen(&self) -> HashSet<Pane> {
+        let slots = self.chart_slots();
+        let mut out = self.hidden.clone();
+        out.extend(TOP_PANES.into_iter().filter(|p| !self.top_row.contains(p)));
+        out.extend(CHART_ROWS.into_iter().filter(|p| !slots.contains(p)));
+        out
+    }
+
+    /// Stacked -> first chart -> next ... -> last -> stacked again. A focused chart
+    /// hands focus to the one now on screen.
+    fn cycle_chart(&mut self) {
+        let shown = self.charts_shown();
+        let cur = self
+            .chart_selection
+            .and_then(|i| shown.iter().position(|&p| p == CHART_ROWS[i]));
+        let next = match cur {
+            None => shown.first(),
+            Some(i) => shown.get(i + 1),
+        };
+        self.chart_selection = next.and_then(|p| CHART_ROWS.iter().position(|q| q == p));
+        if let Some(&p) = next
+            && CHART_ROWS.contains(&self.focused)
+        {
+            self.focused = p;
+        }
+    }
+
+    /// Leave fullscreen and focus `home`, bringing it into the chart slot if needed.
+    /// A hidden home pane, or one left out of the top row, leaves focus where it is.
+    fn go_home(&mut self, home: Pane) {
+        self.fullscreen = None;
+        if self.hidden.contains(&home) || TOP_PANES.contains(&home) && !self.top_row.contains(&home)
+        {
+            return;
+        }
+        if self.chart_selection.is_some()
+            && let Some(i) = CHART_ROWS.iter().position(|&p| p == home)
+        {
+            self.chart_selection = Some(i);
+        }
+        self.focused = home;
+    }
+
+    /// On a new airframe: its profile's focus and fullscreen, or back to `home` after
+    /// one that had them. Hand-made changes since the last airframe win.
+    fn apply_airframe_view(&mut self, cfg: &Config) {
+        if std::mem::take(&mut self.manual_view) {
+            self.profile_view = false;
+            return;
+        }
+        let a = self.last.name.as_deref().and_then(|n| cfg.airframes.get(n));
+        match a.filter(|a| a.focus.is_some() || a.fullscreen) {
+            Some(a) => {
+                self.go_home(a.focus.unwrap_or(cfg.home));
+                if a.fullscreen {
+                    self.fullscreen = Some(self.focused);
+                }
+                self.profile_view = true;
+            }
+            None if self.profile_view => {
+                self.go_home(cfg.home);
+                self.profile_view = false;
+            }
+            None => {}
+        }
+    }
+
+    /// alerts.focus: fullscreen the pane for the most pressing warning, and put the
+    /// previous view back once warnings clear, unless focus was moved meanwhile.
+    fn follow_warnings(&mut self, cfg: &Config) {
+        if !cfg.alerts.focus {
+            return;
+        }
+        let want = self.warnings.pane();
+        if want == self.alert_view.map(|(p, ..)| p) {
+            return;
+        }
+        match want {
+            Some(pane) => {
+                let (_, focused, fullscreen) =
+                    self.alert_view
+                        .unwrap_or((pane, self.focused, self.fullscreen));
+                self.alert_view = Some((pane, focused, fullscreen));
+                self.focused = pane;
+                self.fullscreen = Some(pane);
+            }
+            None => {
+                if let Some((pane, focused, fullscreen)) = self.alert_view.take()
+                    && self.focused == pane
+                {
+                    self.focused = focused;
+                    self.fullscreen = fullscreen;
+                }
+            }
+        }
+    }
+
+    /// Focus moves and fullscreen, shared by the pad and the keyboard.
+    fn pad_action(&mut self, act: PadAction) {
+        match act {
+            PadAction::Select => {
+                if self.fullscreen == Some(self.focused) {
+                    self.fullscreen = None;
+                } else {
+                    self.fullscreen = Some(self.focused);
+                }
+            }
+            PadAction::Up | PadAction::Down | PadAction::Left | PadAction::Right => {
+                self.focused = self.focus_toward(act);
+            }
+            PadAction::Unknown => return,
+        }
+        self.manual_view = true;
+    }
+
+    /// Where a pad/arrow move from the focused pane lands. Left/Right step through the
+    /// top row in layout order and wrap, as Tab does, so every pane there is reachable;
+    /// on a chart they do nothing. Up/Down follow `move_focus`.
+    fn focus_toward(&self, act: PadAction) -> Pane {
+        let right = match act {
+            PadAction::Left => false,
+            PadAction::Right => true,
+            _ => return move_focus(self.focused, act, &self.off_screen()),
+        };
+        let order = self.top_shown();
+        let n = order.len();
+        if n == 0 || CHART_ROWS.contains(&self.focused) {
+            return self.focused;
+        }
+        match order.iter().position(|&p| p == self.focused) {
+            Some(i) if right => order[(i + 1) % n],
+            Some(i) => order[(i + n - 1) % n],
+            None => order[0],
+        }
+    }
+
+    /// Tab / Shift+Tab: the next (or previous) pane on screen, top row then charts.
+    fn cycle_focus(&mut self, back: bool) {
+        let order: Vec<Pane> = self
+            .top_shown()
+            .into_iter()
+            .chain(self.chart_slots())
+            .collect();
+        let n = order.len();
+        if n == 0 {
+            return;
+        }
+        let next = match order.iter().position(|&p| p == self.focused) {
+            Some(i) if back => (i + n - 1) % n,
+            Some(i) => (i + 1) % n,
+            None => 0,
+        };
+        self.focused = order[next];
+        self.manual_view = true;
+    }
+
+    #[cfg_attr(not(feature = "wacom"), allow(dead_code))]
+    fn note_pad_code(&mut self, code: u16, act: PadAction) {
+        let entry = self.pad_codes.entry(code).or_insert((0, act));
+        entry.0 += 1;
+        entry.1 = act;
+    }
+
+    #[cfg_attr(not(feature = "wacom"), allow(dead_code))]
+    fn note_pad_abs(&mut self, code: u16, value: i32) {
+        let axis = self.pad_abs.entry(code).or_insert(AbsAxis {
+            value,
+            min: value,
+            max: value,
+            events: 0,
+        });
+        axis.value = value;
+        axis.min = axis.min.min(value);
+        axis.max = axis.max.max(value);
+        axis.events += 1;
+    }
+
+    /// The state a frame from `name` folds into: the shown one, or the airframe's own
+    /// track while the shown airframe is still streaming.
+    fn track_mut(&mut self, name: Option<&str>) -> &mut UiState {
+        let Some(name) = name else {
+            return self;
+        };
+        if self.last.name.as_deref().is_none_or(|cur| cur == name) {
+            return self;
+        }
+        let quiet = self
+            .seen
+            .packet
+            .is_none_or(|at| at.elapsed().as_millis() > AIRFRAME_FOLLOW_MS);
+        if quiet {
+            // Like a single stream: fold in, unless this airframe already has a track.
+            if self.airframes.contains_key(name) {
+                self.show_airframe(name);
+            }
+            return self;
+        }
+        let units = self.units;
+        self.airframes
+            .entry(name.to_string())
+            .or_insert_with(|| UiState {
+                units,
+                ..UiState::default()
+            })
+    }
+
+    /// Show the next tracked airframe by name, wrapping around.
+    fn cycle_airframe(&mut self) {
+        let cur = self.last.name.clone().unwrap_or_default();
+        let next = self
+            .airframes
+            .keys()
+            .find(|n| **n > cur)
+            .or_else(|| self.airframes.keys().next())
+            .cloned();
+        if let Some(next) = next {
+            self.show_airframe(&next);
+        }
+    }
+
+    /// Swap `name`'s track in, parking the shown one under its own name.
+    fn show_airframe(&mut self, name: &str) {
+        let Some(mut other) = self.airframes.remove(name) else {
+            return;
+        };
+        self.swap_track(&mut other);
+        if let Some(parked) = other.last.name.clone() {
+            self.airframes.insert(parked, other);
+        }
+    }
+
+    /// Exchange everything derived from one airframe's telemetry with `other`, leaving
+    /// view settings (focus, layout, units, popups) alone.
+    fn swap_track(&mut self, other: &mut UiState) {
+        use std::mem::swap;
+        swap(&mut self.last, &mut other.last);
+        swap(&mut self.raw, &mut other.raw);
+        swap(&mut self.ias_hist, &mut other.ias_hist);
+        swap(&mut self.alt_hist, &mut other.alt_hist);
+        swap(&mut self.mach_hist, &mut other.mach_hist);
+        swap(&mut self.ps_hist, &mut other.ps_hist);
+        swap(&mut self.vv_hist, &mut other.vv_hist);
+        swap(&mut self.g_hist, &mut other.g_hist);
+        swap(&mut self.g_session, &mut other.g_session);
+        swap(&mut self.hist_times, &mut other.hist_times);
+        swap(&mut self.warnings, &mut other.warnings);
+        swap(&mut self.seen, &mut other.seen);
+        swap(&mut self.fpa_hist, &mut other.fpa_hist);
+        swap(&mut self.smooth_g, &mut other.smooth_g);
+        swap(&mut self.smooth_aoa, &mut other.smooth_aoa);
+        swap(&mut self.gs, &mut other.gs);
+        swap(&mut self.ps, &mut other.ps);
+        swap(&mut self.alt_prev, &mut other.alt_prev);
+        swap(&mut self.extremes, &mut other.extremes);
+        swap(&mut self.chart_acc, &mut other.chart_acc);
+        swap(&mut self.flight_log, &mut other.flight_log);
+        swap(&mut self.max_split, &mut other.max_split);
+        swap(&mut self.alt_datum, &mut other.alt_datum);
+    }
+
+    /// Forget all telemetry, as if none had arrived yet; the view and the `z` datum stay.
+    fn rewind(&mut self) {
+        let mut fresh = UiState {
+            alt_datum: self.alt_datum,
+            ..UiState::default()
+        };
+        self.swap_track(&mut fresh);
+        self.airframes.clear();
+        self.alert_view = None;
+    }
+
+    /// Pin/unpin the focused pane, snapshotting what it currently shows.
+    fn toggle_pin(&mut self) {
+        let pane = self.focused;
+        if self.pinned.remove(&pane).is_none() {
+            self.pinned.insert(pane, self.snapshot());
+        }
+    }
+
+    /// A copy for drawing one pane later, without the nested copies and other tracks.
+    fn snapshot(&self) -> UiState {
+        let mut snap = self.clone();
+        snap.pinned.clear();
+        snap.held.clear();
+        snap.airframes.clear();
+        snap
+    }
+}
+
+// ---------------- Warnings ----------------
+
+// A warning clears only once the value is this fraction back past its threshold,
+// so values hovering at the limit don't make it flicker.
+const WARN_HYSTERESIS: f64 = 0.05;
+
+#[derive(Debug, Clone, Default)]
+struct Warnings {
+    low_fuel: bool,
+    over_g: bool,
+    /// Below limits.min_agl_m and descending.
+    low_agl: bool,
+    /// Engine L/R split past limits.rpm_split / limits.temp_split.
+    rpm_split: bool,
+    temp_split: bool,
+    /// G past limits.envelope at the current AoA.
+    envelope: bool,
+    /// Altitude changing faster than limits.alt_rate_max for long enough.
+    alt_rate: bool,
+    /// Consecutive altitude samples over the rate limit.
+    alt_rate_run: u32,
+}
+
+impl Warnings {
+    /// Pane showing the most pressing active warning, for alerts.focus.
+    fn pane(&self) -> Option<Pane> {
+        [
+            (self.low_agl, Pane::Flight),
+            (self.over_g || self.envelope, Pane::Att),
+            (self.alt_rate, Pane::AltChart),
+            (
+                self.low_fuel || self.rpm_split || self.temp_split,
+                Pane::Systems,
+            ),
+        ]
+        .into_iter()
+        .find(|&(on, _)| on)
+        .map(|(_, pane)| pane)
+    }
+}
+
+// Minimum spacing between terminal bells.
+const BELL_INTERVAL_MS: u64 = 1000;
+
+/// Re-evaluate warnings against a new frame. Missing data clears the warning, except
+/// `alt_rate`, which is only Some on frames that brought a new altitude sample.
+fn update_warnings(w: &mut Warnings, t: &Telemetry, limits: &Limits, alt_rate: Option<f64>) {
+    w.low_fuel = match (t.fuel_kg, limits.low_fuel_kg) {
+        (Some(fuel), Some(min)) if w.low_fuel => fuel < min * (1.0 + WARN_HYSTERESIS),
+        (Some(fuel), Some(min)) => fuel < min,
+        _ => false,
+    };
+    let g = t.accel.as_ref().and_then(|a| a.y);
+    w.over_g = match (g, limits.g_max) {
+        (Some(g), Some(max)) if w.over_g => g > max * (1.0 - WARN_HYSTERESIS),
+        (Some(g), Some(max)) => g > max,
+        _ => false,
+    };
+    let limit = t.aoa_rad.and_then(|a| limits.envelope_g(a.to_degrees()));
+    w.envelope = match (g, limit) {
+        (Some(g), Some(max)) if w.envelope => g > max * (1.0 - WARN_HYSTERESIS),
+        (Some(g), Some(max)) => g > max,
+        _ => false,
+    };
+    // Climbing or level clears it, so a flare or a pull-out doesn't keep it on.
+    w.low_agl = match (t.alt_agl, t.vv_ms, limits.min_agl_m) {
+        (Some(agl), Some(vv), Some(min)) if vv < 0.0 && w.low_agl => {
+            agl < min * (1.0 + WARN_HYSTERESIS)
+        }
+        (Some(agl), Some(vv), Some(min)) => vv < 0.0 && agl < min,
+        _ => false,
+    };
+    // Single-packet spikes (a teleport, a jittery exporter) need company to count.
+    match (alt_rate.map(f64::abs), limits.alt_rate_max) {
+        (Some(rate), Some(max)) => {
+            let over = if w.alt_rate {
+                rate > max * (1.0 - WARN_HYSTERESIS)
+            } else {
+                rate > max
+            };
+            w.alt_rate_run = if over { w.alt_rate_run + 1 } else { 0 };
+            w.alt_rate = w.alt_rate_run >= limits.alt_rate_samples.unwrap_or(ALT_RATE_SAMPLES);
+        }
+        (_, None) => {
+            w.alt_rate = false;
+            w.alt_rate_run = 0;
+        }
+        (None, Some(_)) => {}
+    }
+    let e = t.engine.as_ref();
+    let split = |p: Option<&Pair>, max: Option<f64>, on: bool| match (
+        p.and_then(Pair::split).map(f64::abs),
+        max,
+    ) {
+        (Some(d), Some(max)) if on => d > max * (1.0 - WARN_HYSTERESIS),
+        (Some(d), Some(max)) => d > max,
+        _ => false,
+    };
+    w.rpm_split = split(
+        e.and_then(|e| e.rpm.as_ref()),
+        limits.rpm_split,
+        w.rpm_split,
+    );
+    w.temp_split = split(
+        e.and_then(|e| e.temp.as_ref()),
+        limits.temp_split,
+        w.temp_split,
+    );
+}
+
+/// Largest engine L/R splits (absolute) seen this session.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+struct MaxSplit {
+    rpm: Option<f64>,
+    temp: Option<f64>,
+}
+
+impl MaxSplit {
+    fn update(&mut self, e: &Engine) {
+        let max = |cur: Option<f64>, p: &Option<Pair>| match p.as_ref().and_then(Pair::split) {
+            Some(d) => Some(cur.map_or(d.abs(), |c| c.max(d.abs()))),
+            None => cur,
+        };
+        self.rpm = max(self.rpm, &e.rpm);
+        self.temp = max(self.temp, &e.temp);
+    }
+}
+
+/// Session [min, max] of each pane's primary value, in SI units; `X` resets them.
+#[derive(Debug, Clone, Default)]
+struct Extremes {
+    ias: Option<[f64; 2]>,
+    g: Option<[f64; 2]>,
+    rpm: Option<[f64; 2]>,
+    alt: Option<[f64; 2]>,
+    mach: Option<[f64; 2]>,
+    ps: Option<[f64; 2]>,
+    vv: Option<[f64; 2]>,
+}
+
+impl Extremes {
+    fn update(&mut self, t: &Telemetry, ps: Option<f64>) {
+        let widen = |e: &mut Option<[f64; 2]>, v: Option<f64>| {
+            if let Some(v) = v.filter(|v| v.is_finite()) {
+                *e = Some(e.map_or([v, v], |[lo, hi]| [lo.min(v), hi.max(v)]));
+            }
+        };
+        widen(&mut self.ias, t.ias_ms);
+        widen(&mut self.g, t.accel.as_ref().and_then(|a| a.y));
+        let rpm = t.engine.as_ref().and_then(|e| e.rpm.as_ref());
+        widen(&mut self.rpm, rpm.and_then(|p| p.L));
+        widen(&mut self.rpm, rpm.and_then(|p| p.R));
+        widen(&mut self.alt, t.alt_msl);
+        widen(&mut self.mach, t.mach);
+        widen(&mut self.ps, ps);
+        widen(&mut self.vv, t.vv_ms);
+    }
+
+    /// " [IAS 280–340]" for the pane's primary value, once it has one.
+    fn title(&self, pane: Pane, units: Units) -> Option<String> {
+        let alt = units.alt_scale();
+        let (label, [lo, hi], scale, prec) = match pane {
+            Pane::Flight | Pane::IasChart => ("IAS", self.ias?, units.speed_scale(), 0),
+            Pane::Att | Pane::GChart => ("G", self.g?, 1.0, 1),
+            Pane::Systems => ("RPM", self.rpm?, 1.0, 0),
+            Pane::AltChart => ("ALT", self.alt?, alt, 0),
+            Pane::MachChart => ("M", self.mach?, 1.0, 2),
+            Pane::PsChart => ("Ps", self.ps?, alt, 0),
+            Pane::VvChart => {
+                let scale = Pane::VvChart.chart_spec(units)?.scale;
+                ("VV", self.vv?, scale, usize::from(scale == 1.0))
+            }
+            Pane::Inputs | Pane::Computed | Pane::Stats => return None,
+        };
+        Some(format!(
+            " [{label} {:.prec$}–{:.prec$}]",
+            lo * scale,
+            hi * scale
+        ))
+    }
+}
+
+fn warning_spans(w: &Warnings, cfg: &Config) -> Vec<Span<'static>> {
+    let alert = cfg.style(Style::default().fg(Color::Black).bg(Color::Red).bold());
+    let caution = cfg.style(Style::default().fg(Color::Black).bg(Color::Yellow).bold());
+    let mut spans = Vec::new();
+    if w.low_fuel {
+        spans.push(Span::styled(" LOW FUEL ", alert));
+        spans.push(Span::raw(" "));
+    }
+    if w.over_g {
+        spans.push(Span::styled(" OVER G ", alert));
+        spans.push(Span::raw(" "));
+    }
+    if w.envelope {
+        spans.push(Span::styled(" ENVELOPE ", caution));
+        spans.push(Span::raw(" "));
+    }
+    if w.alt_rate {
+        spans.push(Span::styled(" ALT RATE ", caution));
+        spans.push(Span::raw(" "));
+    }
+    if w.low_agl && cfg.alerts.pull_up {
+        spans.push(Span::styled(" PULL UP ", alert));
+        spans.push(Span::raw(" "));
+    }
+    spans
+}
+
+/// "NO DATA (stale 7.3s)" while the link is down; see `UiState::link_stale`.
+fn stale_spans(s: &UiState, cfg: &Config) -> Vec<Span<'static>> {
+    let Some(age) = s.link_stale(cfg) else {
+        return vec![];
+    };
+    vec![
+        Span::styled(
+            format!("NO DATA (stale {age:.1}s)"),
+            cfg.style(Style::default().fg(Color::Red).bold()),
+        ),
+        Span::raw("   "),
+    ]
+}
+
+// ---------------- Computed fields ----------------
+
+/// A parsed [computed] expression: numbers, telemetry fields, + - * / and parentheses.
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(f64),
+    /// Dotted path into the merged telemetry JSON, e.g. `accel.y` or `engine.rpm.L`.
+    Field(String),
+    Neg(Box<Expr>),
+    Bin(char, Box<Expr>, Box<Expr>),
+}
+
+/// A [computed] entry, parsed once when the config loads; `problems()` reports errors.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "String")]
+struct Computed(Result<Expr, String>);
+
+impl From<String> for Computed {
+    fn from(src: String) -> Self {
+        Computed(Expr::parse(&src))
+    }
+}
+
+impl Expr {
+    fn parse(src: &str) -> Result<Expr, String> {
+        let tokens = tokenize(src)?;
+        let mut pos = 0;
+        let e = parse_sum(&tokens, &mut pos)?;
+        match tokens.get(pos) {
+            None => Ok(e),
+            Some(t) => Err(format!("unexpected {t}")),
+        }
+    }
+
+    /// None if a field is missing or not a number, or the result isn't finite.
+    fn eval(&self, raw: &Value) -> Option<f64> {
+        let v = match self {
+            Expr::Num(n) => *n,
+            Expr::Field(path) => path
+                .split('.')
+                .try_fold(raw, |v, key| v.get(key))?
+                .as_f64()?,
+            Expr::Neg(e) => -e.eval(raw)?,
+            Expr::Bin(op, l, r) => {
+                let (l, r) = (l.eval(raw)?, r.eval(raw)?);
+                match op {
+                    '+' => l + r,
+                    '-' => l - r,
+                    '*' => l * r,
+                    _ => l / r,
+                }
+            }
+        };
+        v.is_finite().then_some(v)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::Num(n) => write!(f, "`{n}`"),
+            Token::Ident(s) => write!(f, "`{s}`"),
+            Token::Op(c) => write!(f, "`{c}`"),
+        }
+    }
+}
+
+/// Accepts × ÷ − as well as * / -.
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut out = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut num = String::new();
+            while let Some(&d) = chars.peek() {
+                // An exponent sign belongs to the number: 1e-3.
+                let exp_sign = (d == '-' || d == '+') && num.ends_with(['e', 'E']);
+                if d.is_ascii_digit() || d == '.' || d == 'e' || d == 'E' || exp_sign {
+                    num.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let n = num.parse().map_err(|_| format!("bad number `{num}`"))?;
+            out.push(Token::Num(n));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut id = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_ascii_alphanumeric() || d == '_' || d == '.' {
+                    id.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            out.push(Token::Ident(id));
+        } else {
+            let op = match c {
+                '+' | '-' | '*' | '/' | '(' | ')' => c,
+                '−' => '-',
+                '×' => '*',
+                '÷' => '/',
+                _ => return Err(format!("unexpected `{c}`")),
+            };
+            out.push(Token::Op(op));
+            chars.next();
+        }
+    }
+    Ok(out)
+}
+
+fn parse_sum(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    let mut e = parse_product(tokens, pos)?;
+    while let Some(Token::Op(op @ ('+' | '-'))) = tokens.get(*pos) {
+        *pos += 1;
+        e = Expr::Bin(*op, Box::new(e), Box::new(parse_product(tokens, pos)?));
+    }
+    Ok(e)
+}
+
+fn parse_product(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    let mut e = parse_unary(tokens, pos)?;
+    while let Some(Token::Op(op @ ('*' | '/'))) = tokens.get(*pos) {
+        *pos += 1;
+        e = Expr::Bin(*op, Box::new(e), Box::new(parse_unary(tokens, pos)?));
+    }
+    Ok(e)
+}
+
+fn parse_unary(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    let t = tokens.get(*pos).ok_or("unexpected end")?;
+    *pos += 1;
+    match t {
+        Token::Num(n) => Ok(Expr::Num(*n)),
+        Token::Ident(id) => Ok(Expr::Field(id.clone())),
+        Token::Op('-') => Ok(Expr::Neg(Box::new(parse_unary(tokens, pos)?))),
+        Token::Op('+') => parse_unary(tokens, pos),
+        Token::Op('(') => {
+            let e = parse_sum(tokens, pos)?;
+            match tokens.get(*pos) {
+                Some(Token::Op(')')) => {
+                    *pos += 1;
+                    Ok(e)
+                }
+                _ => Err("missing `)`".into()),
+            }
+        }
+        t => Err(format!("unexpected {t}")),
+    }
+}
+
+// ---------------- Flight log ----------------
+
+// Events kept; the oldest drop off.
+const FLIGHT_LOG_CAP: usize = 200;
+// A mech part is logged once it stops moving (less than MECH_SETTLE per packet) at a
+// position more than MECH_DEADBAND from the last one logged, so travel and noise
+// don't spam the log.
+const MECH_SETTLE: f64 = 0.005;
+const MECH_DEADBAND: f64 = 0.05;
+
+/// Mech fields watched for transitions, with their log labels.
+fn mech_parts(m: &Mech) -> [(&'static str, Option<f64>); 5] {
+    [
+        ("Gear", m.gear),
+        ("Flaps", m.flaps),
+        ("Airbrake", m.airbrake),
+        ("Hook", m.hook),
+        ("Wing", m.wing),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum FlightEventKind {
+    Takeoff,
+    Landing {
+        /// Vertical speed just before touchdown.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        vv_ms: Option<f64>,
+    },
+    /// A mech part settled at a new position (0..1).
+    Mech {
+        part: &'static str,
+        pos: f64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct FlightEvent {
+    wall_ms: u128,
+    #[serde(flatten)]
+    kind: FlightEventKind,
+}
+
+impl FlightEvent {
+    fn describe(&self) -> String {
+        match self.kind {
+            FlightEventKind::Takeoff => "Takeoff".into(),
+            FlightEventKind::Landing { vv_ms: None } => "Landing".into(),
+            FlightEventKind::Landing { vv_ms: Some(v) } => {
+                format!("Landing  VV {v:>5.1} m/s ({:.0} fpm)", v * M_TO_FT * 60.0)
+            }
+            FlightEventKind::Mech { part, pos } => match (part, pos) {
+                ("Gear" | "Hook", p) if p >= 1.0 - MECH_DEADBAND => format!("{part} down"),
+                ("Gear" | "Hook", p) if p <= MECH_DEADBAND => format!("{part} up"),
+                _ => format!("{part} {:.0}%", pos * 100.0),
+            },
+        }
+    }
+}
+
+/// Sortie timeline built from weight-on-wheels and (optionally) mech transitions.
+#[derive(Debug, Clone, Default)]
+struct FlightLog {
+    events: VecDeque<FlightEvent>,
+    /// WoW as of the last packet carrying it; None until then, so starting up
+    /// mid-flight or on the ramp doesn't log an event.
+    on_ground: Option<bool>,
+    /// Latest VV while airborne, which is the sink rate once WoW sets.
+    air_vv: Option<f64>,
+    /// Per `mech_parts` entry: value in the previous packet, and the last logged one
+    /// (the first settled value is taken as a baseline without logging).
+    mech_prev: [Option<f64>; 5],
+    mech_logged: [Option<f64>; 5],
+}
+
+impl FlightLog {
+    /// `packet` is the frame as received, `t` the merged state. Settling is judged on
+    /// packets that actually carry mech, since a merged value just repeats between them.
+    fn update(&mut self, packet: &Telemetry, t: &Telemetry, mech: bool) {
+        if mech && let Some(m) = &packet.mech {
+            self.update_mech(m);
+        }
+        let Some(wow) = t.mech.as_ref().and_then(|m| m.wow) else {
+            return;
+        };
+        let ground = wow >= 0.5;
+        match (self.on_ground, ground) {
+            (Some(false), true) => self.push(FlightEventKind::Landing { vv_ms: self.air_vv }),
+            (Some(true), false) => self.push(FlightEventKind::Takeoff),
+            _ => {}
+        }
+        self.on_ground = Some(ground);
+        if !ground {
+            self.air_vv = t.vv_ms.or(self.air_vv);
+        }
+    }
+
+    fn update_mech(&mut self, m: &Mech) {
+        for (i, (part, v)) in mech_parts(m).into_iter().enumerate() {
+            let Some(v) = v else {
+                continue;
+            };
+            let settled = self.mech_prev[i].is_some_and(|p| (v - p).abs() < MECH_SETTLE);
+            self.mech_prev[i] = Some(v);
+            if !settled {
+                continue;
+            }
+            match self.mech_logged[i] {
+                None => self.mech_logged[i] = Some(v),
+                Some(l) if (v - l).abs() > MECH_DEADBAND => {
+                    self.mech_logged[i] = Some(v);
+                    self.push(FlightEventKind::Mech { part, pos: v });
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn push(&mut self, kind: FlightEventKind) {
+        self.events.push_back(FlightEvent {
+            wall_ms: unix_ms(SystemTime::now()),
+            kind,
+        });
+        while self.events.len() > FLIGHT_LOG_CAP {
+            self.events.pop_front();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+// ---------------- Small helpers ----------------
+
+fn unix_ms(t: SystemTime) -> u128 {
+    t.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// "14:03:22Z" for a Unix timestamp in ms.
+fn fmt_utc(ms: u128) -> String {
+    let secs = (ms / 1000) % 86_400;
+    format!("{:02}:{:02}:{:02}Z", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn push_hist(q: &mut VecDeque<f64>, v: f64, cap: usize) {
+    q.push_back(v);
+    while q.len() > cap {
+        q.pop_front();
+    }
+}
+/// evdev's name for a button code ("BTN_0"); empty without the wacom feature.
+fn pad_code_name(code: u16) -> String {
+    #[cfg(feature = "wacom")]
+    return format!("{:?}", KeyCode::new(code));
+    #[cfg(not(feature = "wacom"))]
+    {
+        let _ = code;
+        String::new()
+    }
+}
+fn abs_code_name(code: u16) -> String {
+    #[cfg(feature = "wacom")]
+    return format!("{:?}", evdev::AbsoluteAxisCode(code));
+    #[cfg(not(feature = "wacom"))]
+    {
+        let _ = code;
+        String::new()
+    }
+}
+fn push_log(q: &mut VecDeque<String>, line: String) {
+    q.push_back(line);
+    while q.len() > INPUT_LOG_CAP {
+        q.pop_front();
+    }
+}
+fn last_n_scaled(src: &[f64], n: usize, scale: f64) -> Vec<u64> {
+    let len = src.len();
+    let start = len.saturating_sub(n);
+    src.iter()
+        .skip(start)
+        .take(n)
+        .map(|v| (*v * scale).max(0.0) as u64)
+        .collect()
+}
+/// Right-aligned (x, y) points for a `Chart`, so the latest sample sits at the right edge.
+fn last_n_points(src: &[f64], n: usize, scale: f64) -> Vec<(f64, f64)> {
+    let len = src.len();
+    let start = len.saturating_sub(n);
+    let offset = n - (len - start);
+    src.iter()
+        .skip(start)
+        .enumerate()
+        .map(|(i, v)| ((offset + i) as f64, *v * scale))
+        .collect()
+}
+/// Samples to plot in `slots` positions. Without a span: the newest `slots`. With one:
+/// everything inside it, back from `now`, and if that's too many, each bucket keeps its extremes so
+/// spikes survive. `pairs` keeps both (in time order, two slots per bucket, for braille
+/// lines); otherwise the one further from the bucket mean is kept. A `smooth` above 1
+/// first replaces each sample with the mean of it and the ones before it.
+fn chart_samples(
+    hist: &VecDeque<f64>,
+    times: &VecDeque<Instant>,
+    span: Option<Duration>,
+    now: Instant,
+    slots: usize,
+    pairs: bool,
+    smooth: usize,
+) -> Vec<f64> {
+    let n = match span {
+        None => slots,
+        Some(span) => times
+            .iter()
+            .rev()
+            .take_while(|&&t| now.saturating_duration_since(t) <= span)
+            .count(),
+    };
+    let start = hist.len().saturating_sub(n);
+    let window: Vec<f64> = if smooth > 1 {
+        // Trailing average, reaching back before the window where history allows.
+        let lead = start.min(smooth - 1);
+        let raw: Vec<f64> = hist.iter().skip(start - lead).copied().collect();
+        let mut sum = 0.0;
+        let mut avg = Vec::with_capacity(raw.len());
+        for (i, v) in raw.iter().enumerate() {
+            sum += v;
+            if i >= smooth {
+                sum -= raw[i - smooth];
+            }
+            avg.push(sum / (i + 1).min(smooth) as f64);
+        }
+        avg.split_off(lead)
+    } else {
+        hist.iter().skip(start).copied().collect()
+    };
+    let buckets = if pairs { slots / 2 } else { slots };
+    if window.len() <= slots || buckets == 0 {
+        return window;
+    }
+    let mut out = Vec::with_capacity(slots);
+    for b in 0..buckets {
+        let lo = b * window.len() / buckets;
+        let hi = ((b + 1) * window.len() / buckets).max(lo + 1);
+        let chunk = &window[lo..hi];
+        let (mut imin, mut imax) = (0, 0);
+        for (i, v) in chunk.iter().enumerate() {
+            if *v < chunk[imin] {
+                imin = i;
+            }
+            if *v > chunk[imax] {
+                imax = i;
+            }
+        }
+        let (min, max) = (chunk[imin], chunk[imax]);
+        if pairs {
+            out.extend(if imin <= imax { [min, max] } else { [max, min] });
+        } else {
+            let mean = chunk.iter().sum::<f64>() / chunk.len() as f64;
+            out.push(if max - mean >= mean - min { max } else { min });
+        }
+    }
+    out
+}
+#[cfg_attr(not(feature = "wacom"), allow(dead_code))]
+fn fmt_ts(ts: SystemTime) -> (u64, u32) {
+    match ts.duration_since(UNIX_EPOCH) {
+        Ok(d) => (d.as_secs() % 1000, d.subsec_micros()),
+        Err(_) => (0, 0),
+    }
+}
+
+// ---------------- Runtime wiring ----------------
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // Detail goes to stderr only with -v; the startup summary covers the rest.
+    tracing_subscriber::fmt()
+        .with_writer(io::stderr)
+        .with_target(false)
+        .without_time()
+        .with_ansi(!cli.no_color && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()))
+        .with_max_level(if cli.verbose {
+            tracing::Level::DEBUG
+        } else {
+            tracing::Level::WARN
+        })
+        .init();
+    if cli.check_config {
+        return check_config();
+    }
+    if cli.init_config {
+        return init_config(cli.force);
+    }
+    #[cfg(feature = "wacom")]
+    if cli.list_devices {
+        return list_devices();
+    }
+    let mut cfg = load_config()?;
+    // https://no-color.org: any non-empty value disables color.
+    cfg.no_color |= cli.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    if let Ok(v) = std::env::var("FOCUS_COLOR") {
+        match v.parse::<Color>() {
+            Ok(c) => cfg.theme.focus = c,
+            Err(_) => eprintln!("FOCUS_COLOR={v} is not a color name or #rrggbb; using yellow"),
+        }
+    }
+    cfg.history = Some(cli.history);
+    let cfg = Arc::new(cfg);
+    let (tx, rx) = watch::channel(UiState {
+        units: cfg.units,
+        angles: cfg.angles,
+        top_row: cfg.layout.top.clone(),
+        dense: cfg.layout.dense,
+        header: cfg.header.mode,
+        trend: true,
+        aoa_units: true,
+        ..UiState::default()
+    });
+    let state_file = state_path(&cfg).filter(|_| cfg.state.persist);
+
+    let mut startup = vec![match config_path() {
+        Some(p) if p.exists() => format!("Config:  {}", p.display()),
+        _ => "Config:  defaults (no config file)".to_string(),
+    }];
+    if let Some(path) = &state_file {
+        // Broken state only costs the saved view; the config still applies.
+        startup.push(match load_state(path) {
+            Ok(Some(state)) => {
+                tx.send_modify(|s| state.apply(s));
+                format!("State:   {}", path.display())
+            }
+            Ok(None) => format!("State:   {} (new)", path.display()),
+            Err(e) => format!("State:   {} unreadable, ignored: {e:#}", path.display()),
+        });
+    }
+    if let Some(path) = &cli.record {
+        startup.push(format!("Record:  {}", path.display()));
+    }
+    if let Some(addr) = &cli.forward {
+        startup.push(format!("Forward: {addr}"));
+    }
+    let record = cli.record.map(|path| {
+        let (rec_tx, rec_rx) = mpsc::channel(RECORD_QUEUE);
+        let max_bytes = cli.record_max_mb.map(|mb| mb * 1024 * 1024);
+        task::spawn(recorder(
+            path,
+            cli.record_rate,
+            max_bytes,
+            rec_rx,
+            tx.clone(),
+        ));
+        rec_tx
+    });
+    let forward = match &cli.forward {
+        Some(addr) => Some(Arc::new(Forward::open(addr).await?)),
+        None => None,
+    };
+    let (reconnect_tx, reconnect_rx) = watch::channel(());
+    let pipe = Pipeline {
+        tx: tx.clone(),
+        cfg: cfg.clone(),
+        record,
+        forward,
+        reconnect: reconnect_rx,
+        framing: cli.framing,
+    };
+    let mut inputs = cli.input;
+    let mut replay_ctl = None;
+    if cli.demo {
+        task::spawn(demo_generator(pipe.clone()));
+    } else if let Some([a, b]) = cli.compare.as_deref() {
+        startup.push(format!("Compare: {} vs {}", a.display(), b.display()));
+        task::spawn(compare_replay(a.clone(), b.clone(), pipe.clone()));
+    } else if let Some(path) = &cli.replay {
+        startup.push(format!("Replay:  {}", path.display()));
+        let (ctl_tx, ctl_rx) = mpsc::channel(16);
+        task::spawn(replay(path.clone(), pipe.clone(), ctl_rx));
+        replay_ctl = Some(ctl_tx);
+    } else if inputs.is_empty() {
+        inputs.push(Input::Udp(SocketAddr::new(cli.bind, cli.port).to_string()));
+    }
+    if cli.demo {
+        startup.push("Input:   demo generator".to_string());
+    }
+    for input in &inputs {
+        startup.push(format!("Input:   {}", input.describe()));
+        match input {
+            Input::Udp(bind) => task::spawn(udp_listener(bind.clone(), pipe.clone())),
+            #[cfg(unix)]
+            Input::Unix(path) => task::spawn(unix_listener(path.clone(), pipe.clone())),
+            Input::File(path) => {
+                task::spawn(file_listener(path.clone(), cli.from_start, pipe.clone()))
+            }
+            #[cfg(feature = "serial")]
+            Input::Serial { path, baud } => {
+                task::spawn(serial_listener(path.clone(), *baud, pipe.clone()))
+            }
+        };
+    }
+    let reconnect = inputs
+        .iter()
+        .any(Input::reconnectable)
+        .then_some(reconnect_tx);
+    if let Some(path) = cli.status_line {
+        task::spawn(status_line_writer(path, rx.clone()));
+    }
+    if cfg.charts.sample_ms > 0 {
+        task::spawn(chart_ticker(tx.clone(), cfg.clone()));
+    }
+    // Optional Wacom: start movement logic only if a device is available right now.
+    #[cfg(feature = "wacom")]
+    {
+        if let Some((path, dev)) = find_wacom_pad() {
+            startup.push(format!("Wacom:   {path}"));
+            task::spawn(wacom_listener_with_device(
+                tx.clone(),
+                rx.clone(),
+                path,
+                dev,
+                cfg.wacom.clone(),
+            ));
+        } else {
+            startup.push("Wacom:   none found (or no permission); no pad controls".to_string());
+        }
+    }
+    // The alternate screen would hide these, so the TUI shows them in an overlay.
+    if cli.headless {
+        startup.iter().for_each(|l| eprintln!("{l}"));
+    } else if cfg.splash.secs > 0.0 {
+        tx.send_modify(|s| s.splash = Some((Instant::now(), startup)));
+    }
+
+    let result = if cli.headless {
+        run_headless(rx, cli.summary_interval).await
+    } else {
+        let last = rx.clone();
+        let result = run_tui(tx, rx, cfg, reconnect, replay_ctl, cli.tick_ms).await;
+        if let Some(path) = &state_file
+            && let Err(e) = save_state(path, &last.borrow())
+        {
+            eprintln!("Cannot save state to {}: {e:#}", path.display());
+        }
+        result
+    };
+    #[cfg(unix)]
+    for input in &inputs {
+        if let Input::Unix(path) = input {
+            let _ = remove_stale_socket(path);
+        }
+    }
+    result
+}
+
+#[cfg(feature = "wacom")]
+async fn wacom_listener_with_device(
+    tx: watch::Sender<UiState>,
+    rx: watch::Receiver<UiState>,
+    path: String,
+    mut dev: Device,
+    wacom: WacomConfig,
+) {
+    // For logging context
+    let mut last_side_hint = Side::Left;
+    let mut last_abs_misc: i32 = 0;
+    let mut last_abs_at = Instant::now() - Duration::from_millis(SIDE_TIMEOUT_MS as u64 + 1);
+
+    loop {
+        match dev.fetch_events() {
+            Ok(iter) => {
+                let mut saw = false;
+                for ev in iter {
+                    saw = true;
+
+                    if ev.event_type() == EventType::ABSOLUTE {
+                        if let Some(s) = side_from_abs(ev.code(), ev.value(), wacom.abs_deadzone) {
+                            last_side_hint = s;
+                            last_abs_at = Instant::now();
+                        }
+                        if ev.code() == 40 {
+                            last_abs_misc = ev.value();
                         }
+                        tx.send_modify(|s| s.note_pad_abs(ev.code(), ev.value()));
+                    }
+
+                    if ev.event_type() == EventType::KEY && ev.value() == 1 {
+                        let code_u16 = ev.code();
+                        let act = map_btn_code(code_u16, &wacom.buttons);
+
+                        let mut state = rx.borrow().clone();
+                        state.pad_action(act);
+
+                        let side_for_log = side_from_code(code_u16)
+                            .or_else(|| {
+                                if last_abs_at.elapsed().as_millis() <= SIDE_TIMEOUT_MS {
+                                    Some(last_side_hint)
+                                } else {
+                                    None
+                                }
+                            })
+                            .unwrap_or(last_side_hint);
+
+                        let (s, us) = fmt_ts(ev.timestamp());
+                        push_log(
+                            &mut state.input_log,
+                            format!(
+                                "[{:>3}.{:06}] {:?} (code={}, ABS_MISC={}) -> {:?} ({:?} side)",
+                                s,
+                                us,
+                                KeyCode::new(code_u16),
+                                code_u16,
+                                last_abs_misc,
+                                act,
+                                side_for_log
+                            ),
+                        );
+                        state.note_pad_code(code_u16, act);
+                        let _ = tx.send(state);
+                    }
+                }
+                if !saw {
+                    sleep(Duration::from_millis(10)).await;
+                }
+            }
+            Err(e) => {
+                eprintln!("Wacom read error ({}): {}", path, e);
+                sleep(Duration::from_millis(300)).await;
+            }
+        }
+    }
+}
 
-                        let side_for_log = side_from_code(code_u16)
-                            .or_else(|| {
-                                if last_abs_at.elapsed().as_millis() <= SIDE_TIMEOUT_MS {
-                                    Some(last_side_hint)
-                                } else {
-                                    None
-                                }
-                            })
-                            .unwrap_or(last_side_hint);
+/// Everything an input task needs to hand a packet on: the shared state, config, and
+/// the optional recorder queue and forward target.
+#[derive(Clone)]
+struct Pipeline {
+    tx: watch::Sender<UiState>,
+    cfg: Arc<Config>,
+    record: Option<mpsc::Sender<RecFrame>>,
+    forward: Option<Arc<Forward>>,
+    /// Changes when `r` asks connection-oriented inputs to reopen now.
+    reconnect: watch::Receiver<()>,
+    /// Frame delimiting for datagram inputs.
+    framing: Framing,
+}
+
+/// Resolves on the next reconnect request; never, if nothing can send one (headless).
+async fn reconnect_requested(rx: &mut watch::Receiver<()>) {
+    if rx.changed().await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+impl Input {
+    /// Inputs with something to reopen; datagram sockets just keep listening.
+    fn reconnectable(&self) -> bool {
+        match self {
+            Input::File(_) => true,
+            #[cfg(feature = "serial")]
+            Input::Serial { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// One-line description for the startup overlay.
+    fn describe(&self) -> String {
+        match self {
+            Input::Udp(bind) => format!("UDP {bind}"),
+            #[cfg(unix)]
+            Input::Unix(path) => format!("Unix socket {}", path.display()),
+            Input::File(path) => format!("file {}", path.display()),
+            #[cfg(feature = "serial")]
+            Input::Serial { path, baud } => format!("serial {path} at {baud} baud"),
+        }
+    }
+}
+
+/// Downstream copy of the raw stream for `--forward`.
+struct Forward {
+    sock: UdpSocket,
+    to: SocketAddr,
+    failing: AtomicBool,
+}
+
+impl Forward {
+    async fn open(addr: &str) -> Result<Self> {
+        let to = tokio::net::lookup_host(addr)
+            .await
+            .ok()
+            .and_then(|mut it| it.next())
+            .with_context(|| format!("cannot resolve --forward address {addr}"))?;
+        let local = if to.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let sock = UdpSocket::bind(local)
+            .await
+            .context("cannot open socket for --forward")?;
+        Ok(Self {
+            sock,
+            to,
+            failing: AtomicBool::new(false),
+        })
+    }
+
+    /// Send without waiting; errors are logged once per failure streak and never
+    /// hold up local processing.
+    fn send(&self, bytes: &[u8]) {
+        match self.sock.try_send_to(bytes, self.to) {
+            Ok(_) => {
+                if self.failing.swap(false, Ordering::Relaxed) {
+                    eprintln!("Forwarding to {} recovered", self.to);
+                }
+            }
+            Err(e) => {
+                if !self.failing.swap(true, Ordering::Relaxed) {
+                    eprintln!("Forwarding to {} failed: {e}", self.to);
+                }
+            }
+        }
+    }
+}
+
+async fn udp_listener(bind: String, pipe: Pipeline) {
+    let sock = match UdpSocket::bind(&bind).await {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Bind failed on {bind}: {e}");
+            return;
+        }
+    };
+    // Show the resolved port, e.g. when binding :0.
+    let local = sock.local_addr().map(|a| a.to_string()).unwrap_or(bind);
+    let mut buf = vec![0u8; BUF];
+    loop {
+        match sock.recv_from(&mut buf).await {
+            Ok((n, peer)) => {
+                let src = format!("{local} ← {peer}");
+                ingest(&buf[..n], &pipe, &src, pipe.framing)
+            }
+            Err(e) => {
+                eprintln!("UDP recv error: {e}");
+                sleep(Duration::from_millis(200)).await;
+            }
+        }
+    }
+}
+
+/// Remove a socket left at `path` by a previous run, which would make bind fail.
+/// Anything else there (a mistyped data file, say) is an error and stays put.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    match fs::symlink_metadata(path) {
+        Ok(m) if m.file_type().is_socket() => fs::remove_file(path),
+        Ok(_) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "exists and is not a socket",
+        )),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(unix)]
+async fn unix_listener(path: PathBuf, pipe: Pipeline) {
+    if let Err(e) = remove_stale_socket(&path) {
+        eprintln!("Bind failed on {}: {e}", path.display());
+        return;
+    }
+    let sock = match UnixDatagram::bind(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Bind failed on {}: {e}", path.display());
+            return;
+        }
+    };
+    let src = path.display().to_string();
+    let mut buf = vec![0u8; BUF];
+    loop {
+        match sock.recv(&mut buf).await {
+            Ok(n) => ingest(&buf[..n], &pipe, &src, pipe.framing),
+            Err(e) => {
+                eprintln!("Unix socket recv error: {e}");
+                sleep(Duration::from_millis(200)).await;
+            }
+        }
+    }
+}
+
+/// Serial reads split frames arbitrarily, so bytes are buffered until a newline.
+/// The port is reopened after errors so unplugging the adapter isn't fatal, and right
+/// away on a reconnect request.
+#[cfg(feature = "serial")]
+async fn serial_listener(path: String, baud: u32, pipe: Pipeline) {
+    use tokio_serial::SerialPortBuilderExt;
+
+    let mut buf = vec![0u8; BUF];
+    let mut line = Vec::with_capacity(BUF);
+    let mut reported = false;
+    let mut reconnect = pipe.reconnect.clone();
+    loop {
+        let mut port = match tokio_serial::new(&path, baud).open_native_async() {
+            Ok(p) => p,
+            Err(e) => {
+                if !reported {
+                    eprintln!("Cannot open serial port {path}: {e} (waiting for it)");
+                    reported = true;
+                }
+                sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        reported = false;
+        line.clear();
+        let requested = loop {
+            let read = tokio::select! {
+                r = port.read(&mut buf) => r,
+                _ = reconnect_requested(&mut reconnect) => break true,
+            };
+            let n = match read {
+                Ok(0) => break false,
+                Ok(n) => n,
+                Err(e) => {
+                    eprintln!("Serial read error on {path}: {e}");
+                    break false;
+                }
+            };
+            split_lines(&mut line, &buf[..n], |l| {
+                ingest(l, &pipe, &path, Framing::Newline)
+            });
+        };
+        drop(port);
+        if !requested {
+            sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
+
+/// Follow a growing file like `tail -F`. Starts at the end unless `from_start`; a
+/// truncated or replaced file (log rotation) is reread from its beginning. A reconnect
+/// request reopens the path and carries on from the same offset.
+async fn file_listener(path: PathBuf, from_start: bool, pipe: Pipeline) {
+    let src = path.display().to_string();
+    let mut buf = vec![0u8; BUF];
+    let mut line = Vec::with_capacity(BUF);
+    let mut skip_existing = !from_start;
+    let mut resume_at: Option<u64> = None;
+    let mut reported = false;
+    let mut reconnect = pipe.reconnect.clone();
+    loop {
+        let mut file = match tokio::fs::File::open(&path).await {
+            Ok(f) => f,
+            Err(e) => {
+                if !reported {
+                    eprintln!("Cannot open {src}: {e} (waiting for it)");
+                    reported = true;
+                }
+                sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        reported = false;
+        let (id, len) = match file.metadata().await {
+            Ok(m) => (file_id(&m), m.len()),
+            Err(_) => (None, 0),
+        };
+        let mut pos = 0;
+        let seek_to = match resume_at.take() {
+            // Resume only if the file hasn't shrunk meanwhile.
+            Some(at) if at <= len => Some(io::SeekFrom::Start(at)),
+            _ if std::mem::take(&mut skip_existing) => Some(io::SeekFrom::End(0)),
+            _ => None,
+        };
+        if let Some(to) = seek_to {
+            match file.seek(to).await {
+                Ok(at) => pos = at,
+                Err(e) => eprintln!("Cannot seek {src}: {e}"),
+            }
+        }
+        line.clear();
+        loop {
+            let read = tokio::select! {
+                r = file.read(&mut buf) => r,
+                _ = reconnect_requested(&mut reconnect) => {
+                    // Reread the unfinished line rather than lose it.
+                    resume_at = Some(pos - line.len() as u64);
+                    break;
+                }
+            };
+            match read {
+                Ok(0) => {
+                    sleep(Duration::from_millis(TAIL_POLL_MS)).await;
+                    // Reopen if the path now names a different or shorter file.
+                    match tokio::fs::metadata(&path).await {
+                        Ok(m) if m.len() < pos || file_id(&m) != id => break,
+                        _ => {}
+                    }
+                }
+                Ok(n) => {
+                    pos += n as u64;
+                    split_lines(&mut line, &buf[..n], |l| {
+                        ingest(l, &pipe, &src, Framing::Newline)
+                    });
+                }
+                Err(e) => {
+                    eprintln!("Read error on {src}: {e}");
+                    sleep(Duration::from_secs(1)).await;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn file_id(m: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((m.dev(), m.ino()))
+}
+
+// Without inode numbers only truncation is detected.
+#[cfg(not(unix))]
+fn file_id(_: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Append a chunk from a byte stream to `line`, calling `emit` for each complete line.
+/// An overlong line is cut short at `BUF` bytes and then fails to parse.
+fn split_lines(line: &mut Vec<u8>, chunk: &[u8], mut emit: impl FnMut(&[u8])) {
+    for &b in chunk {
+        if b == b'\n' {
+            emit(line);
+            line.clear();
+        } else if line.len() < BUF {
+            line.push(b);
+        }
+    }
+}
+
+/// Length-prefixed payloads in a datagram, and whether it ended cleanly: a short
+/// length or a payload running past the end drops the rest.
+fn length_frames(mut bytes: &[u8]) -> (Vec<&[u8]>, bool) {
+    let mut out = Vec::new();
+    while !bytes.is_empty() {
+        let Some((len, rest)) = bytes.split_first_chunk::<4>() else {
+            return (out, false);
+        };
+        let len = u32::from_be_bytes(*len) as usize;
+        if len > rest.len() {
+            return (out, false);
+        }
+        let (payload, rest) = rest.split_at(len);
+        out.push(payload);
+        bytes = rest;
+    }
+    (out, true)
+}
+
+/// Parse a datagram of JSON frames, delimited per `framing`, and fold each into the
+/// state. `src` describes where the bytes came from, for the header.
+fn ingest(bytes: &[u8], pipe: &Pipeline, src: &str, framing: Framing) {
+    if let Some(fwd) = &pipe.forward {
+        fwd.send(bytes);
+    }
+    let mut frames = Vec::new();
+    let mut rejected = 0;
+    let mut error = None;
+    let reject = |reason: String, frame: &[u8]| {
+        let text = String::from_utf8_lossy(frame);
+        let mut excerpt: String = text.chars().take(RX_EXCERPT_CHARS).collect();
+        if text.chars().count() > RX_EXCERPT_CHARS {
+            excerpt.push('…');
+        }
+        Some(RxError {
+            wall: SystemTime::now(),
+            reason,
+            excerpt,
+        })
+    };
+    let payloads = match framing {
+        Framing::Newline => bytes.split(|&b| b == b'\n').collect(),
+        Framing::Length => {
+            let (payloads, clean) = length_frames(bytes);
+            if !clean {
+                rejected += 1;
+                error = reject("bad length prefix".into(), bytes);
+            }
+            payloads
+        }
+    };
+    for payload in payloads {
+        let payload = payload.trim_ascii();
+        if payload.is_empty() {
+            continue;
+        }
+        let parsed = serde_json::from_slice::<Value>(payload)
+            .and_then(|patch| Ok((Telemetry::deserialize(&patch)?, patch)));
+        let frame = match parsed {
+            Ok(frame) => frame,
+            Err(e) => {
+                rejected += 1;
+                error = reject(e.to_string(), payload);
+                continue;
+            }
+        };
+        if let Some(rec) = &pipe.record {
+            // Recordings hold one frame per line, so multi-line payloads are compacted.
+            let line = match std::str::from_utf8(payload) {
+                Ok(text) if !text.contains('\n') => text.to_string(),
+                _ => frame.1.to_string(),
+            };
+            // Never block input on disk; a full queue just loses that frame.
+            let _ = rec.try_send(RecFrame {
+                at: Instant::now(),
+                wall: SystemTime::now(),
+                line,
+            });
+        }
+        frames.push(frame);
+    }
+    pipe.tx.send_modify(|state| {
+        state
+            .rx_stats
+            .note(bytes.len(), frames.len(), rejected, error);
+        if state.rx_from.as_deref() != Some(src) {
+            state.rx_from = Some(src.to_string());
+        }
+        let shown = state.last.name.clone();
+        let clock = state.replay_clock;
+        for (t, patch) in frames {
+            let name = t.name.clone();
+            let track = state.track_mut(name.as_deref());
+            apply_telemetry(track, t, patch, &pipe.cfg, clock);
+        }
+        if state.last.name != shown {
+            state.apply_airframe_view(&pipe.cfg);
+        }
+        state.follow_warnings(&pipe.cfg);
+    });
+}
+
+/// `t` is the packet on its own (for charts/freshness); `patch` is its raw JSON, merged
+/// into the accumulated state. `clock` is the frame's recording time when replaying; Ps
+/// and the charts run on it, so a seek's burst of frames charts as it was flown.
+fn apply_telemetry(
+    state: &mut UiState,
+    t: Telemetry,
+    patch: Value,
+    cfg: &Config,
+    clock: Option<Instant>,
+) {
+    let now = Instant::now();
+    let at = clock.unwrap_or(now);
+    if let (Some(alt), Some(tas)) = (t.alt_msl, t.tas_ms) {
+        state.ps.update(alt, tas, at);
+    }
+    let mut alt_rate = None;
+    if let Some(alt) = t.alt_msl {
+        match state.alt_prev {
+            // Several frames in one read (a burst, a replay) would give absurd rates.
+            Some((at, _)) if now.saturating_duration_since(at) < ALT_RATE_MIN_DT => {}
+            prev => {
+                if let Some((at, prev)) = prev {
+                    let dt = now.saturating_duration_since(at).as_secs_f64();
+                    alt_rate = Some((alt - prev) / dt);
+                }
+                state.alt_prev = Some((now, alt));
+            }
+        }
+    }
+    let g = t.accel.as_ref().and_then(|a| a.y);
+    // A new airframe (slot change) starts its own G session.
+    if t.name.is_some() && t.name != state.last.name {
+        state.g_session = None;
+    }
+    if let Some(g) = g.filter(|g| g.is_finite()) {
+        state.g_session = Some(
+            state
+                .g_session
+                .map_or([g, g], |[lo, hi]| [lo.min(g), hi.max(g)]),
+        );
+    }
+    if let Some(fpa) = t.fpa_rad() {
+        state.fpa_hist.push_back((now, fpa));
+        while state.fpa_hist.len() > FPA_HIST {
+            state.fpa_hist.pop_front();
+        }
+    }
+    let sm = &cfg.smoothing;
+    if let Some(g) = g {
+        state
+            .smooth_g
+            .update(g, now, Duration::from_secs_f64(sm.g_ms / 1000.0));
+    }
+    if let Some(aoa) = t.aoa_rad {
+        state
+            .smooth_aoa
+            .update(aoa, now, Duration::from_secs_f64(sm.aoa_ms / 1000.0));
+    }
+    if let (Some(lat), Some(lon)) = (t.lat, t.lon) {
+        state.gs.update(lat, lon, now);
+    }
+    if let Some(seq) = t.seq {
+        state.seq.update(seq);
+    }
+    state.seen.update(&t, now);
+    state.extremes.update(&t, state.ps.get());
+    merge_json(&mut state.raw, patch);
+    if let Ok(merged) = Telemetry::deserialize(&state.raw) {
+        state.last = merged;
+    }
+    // Only flight packets advance the charts; a systems-only stream must not push zeros.
+    // Values come from the merged state, so a packet with a subset of the flight fields
+    // holds the others instead of dropping them to zero.
+    if t.has_flight() {
+        let m = &state.last;
+        let sample = [
+            m.ias_ms.unwrap_or(0.0),
+            m.alt_msl.unwrap_or(0.0),
+            m.mach.unwrap_or(0.0),
+            state.ps.get().unwrap_or(0.0),
+            m.vv_ms.unwrap_or(0.0),
+            m.accel.as_ref().and_then(|a| a.y).unwrap_or(0.0),
+        ];
+        let every = Duration::from_millis(cfg.charts.sample_ms);
+        if every.is_zero() {
+            state.push_chart_sample(sample, cfg, at);
+        } else {
+            state.chart_acc.add(sample);
+            // chart_ticker averages live packets; a replay does it per sample_ms of its
+            // own time instead.
+            let due = (state.hist_times.back())
+                .is_none_or(|&last| at.saturating_duration_since(last) >= every);
+            if clock.is_some()
+                && due
+                && let Some(avg) = state.chart_acc.take()
+            {
+                state.push_chart_sample(avg, cfg, at);
+            }
+        }
+    }
+    let limits = cfg.limits_for(state.last.name.as_deref());
+    update_warnings(&mut state.warnings, &state.last, &limits, alt_rate);
+    state
+        .flight_log
+        .update(&t, &state.last, cfg.flight_log.mech);
+    if let Some(e) = &state.last.engine {
+        state.max_split.update(e);
+    }
+}
+
+// ---------------- Demo ----------------
+
+/// Plausible, slowly varying telemetry for every pane, fed through the normal ingest
+/// path at the exporter's 10 Hz so recording and forwarding behave as with DCS.
+async fn demo_generator(pipe: Pipeline) {
+    let start = Instant::now();
+    let mut tick = tokio::time::interval(Duration::from_millis(100));
+    loop {
+        tick.tick().await;
+        let t = start.elapsed().as_secs_f64();
+        let ias = 180.0 + 40.0 * (t / 20.0).sin();
+        let vv = 25.0 * (t / 30.0).cos();
+        // Integral of vv: climbs and descends around 3000 m.
+        let alt = 3000.0 + 750.0 * (t / 30.0).sin();
+        let bank = 0.6 * (t / 12.0).sin();
+        let g = 1.0 + 1.5 * (t / 7.0).sin().max(0.0) - 0.3 * (t / 5.0).cos().max(0.0);
+        let rpm = 82.0 + 8.0 * (t / 20.0).sin();
+        // Gear and flaps cycle every two minutes so the transitions are visible.
+        let phase = (t % 120.0) / 120.0;
+        let gear = if phase < 0.1 {
+            1.0
+        } else if phase < 0.15 {
+            1.0 - (phase - 0.1) / 0.05
+        } else {
+            0.0
+        };
+        let frame = serde_json::json!({
+            "name": "DEMO",
+            // ~150 m/s north, ~60 m/s east, so derived ground speed looks sensible.
+            "lat": 43.70 + t * 150.0 / 111_320.0,
+            "lon": 44.03 + t * 60.0 / 80_470.0,
+            "alt_msl": alt,
+            "alt_agl": alt - 500.0,
+            "ias_ms": ias,
+            "tas_ms": ias * (1.0 + alt / 45_000.0),
+            "mach": ias * (1.0 + alt / 45_000.0) / 330.0,
+            "aoa_rad": 0.05 + 0.04 * (g - 1.0),
+            "vv_ms": vv,
+            "fuel_kg": (3200.0 - t * 0.8).max(0.0),
+            "att": { "pitch": vv / ias, "bank": bank, "yaw": (t / 60.0) % std::f64::consts::TAU },
+            "accel": { "x": 0.05, "y": g, "z": 0.02 * bank },
+            "engine": {
+                "rpm": { "L": rpm, "R": rpm - 0.5 },
+                "thrtl": { "L": rpm / 100.0, "R": rpm / 100.0 },
+                "thrtl_est": false,
+                "noz": { "L": 0.4, "R": 0.4 },
+                "noz_present": true,
+                "temp": { "L": 600.0 + 3.0 * rpm, "R": 598.0 + 3.0 * rpm },
+                "fuelf": { "L": 0.4 + rpm / 200.0, "R": 0.4 + rpm / 200.0 },
+                "map": { "L": null, "R": null },
+                "map_present": false,
+            },
+            "mech": {
+                "gear": gear,
+                "flaps": gear * 0.5,
+                "airbrake": if g > 2.0 { 0.6 } else { 0.0 },
+                "hook": 0.0,
+                "wing": 0.0,
+                "wow": 0.0,
+                "wow_guess": false,
+            },
+        });
+        ingest(
+            frame.to_string().as_bytes(),
+            &pipe,
+            "demo",
+            Framing::Newline,
+        );
+    }
+}
+
+// ---------------- Recorder ----------------
+
+struct RecFrame {
+    at: Instant,
+    wall: SystemTime,
+    line: String,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RecStatus {
+    frames: u64,
+    /// Current file: 0 is the --record path itself, n its `.n` part.
+    part: u32,
+}
+
+/// `flight.jsonl` for part 0, then `flight.1.jsonl`, `flight.2.jsonl`, ...
+fn record_part(path: &Path, part: u32) -> PathBuf {
+    if part == 0 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}.{part}.{}", ext.to_string_lossy()),
+        None => format!("{stem}.{part}"),
+    };
+    path.with_file_name(name)
+}
+
+fn open_record(path: &Path) -> io::Result<(BufWriter<fs::File>, u64)> {
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let len = file.metadata()?.len();
+    Ok((BufWriter::new(file), len))
+}
+
+/// Append frames as `{"t_ms":..,"wall_ms":..,"data":{..}}` lines. `t_ms` is monotonic
+/// since recording started and carries on across parts. With `rate`, frames are thinned
+/// on a fixed time grid so bursty input still yields evenly spaced samples; the live
+/// display is unaffected. Flushed every RECORD_FLUSH_MS; past `max_bytes` the next
+/// frame goes to a new part.
+async fn recorder(
+    path: PathBuf,
+    rate: Option<f64>,
+    max_bytes: Option<u64>,
+    mut rx: mpsc::Receiver<RecFrame>,
+    ui: watch::Sender<UiState>,
+) {
+    let mut status = RecStatus::default();
+    let mut file = path.clone();
+    let (mut out, mut size) = match open_record(&file) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("Cannot open recording {}: {e}", file.display());
+            return;
+        }
+    };
+    ui.send_modify(|s| s.recorded = Some(status));
+    let start = Instant::now();
+    let gap = rate.map(|hz| Duration::from_secs_f64(1.0 / hz));
+    let mut next_due: Option<Instant> = None;
+    let mut flush = tokio::time::interval(Duration::from_millis(RECORD_FLUSH_MS));
+    let mut pending = false;
+    loop {
+        let frame = tokio::select! {
+            frame = rx.recv() => match frame {
+                Some(frame) => frame,
+                None => break,
+            },
+            _ = flush.tick() => {
+                if pending {
+                    if let Err(e) = out.flush() {
+                        eprintln!("Recording to {} failed: {e}", file.display());
+                        return;
+                    }
+                    pending = false;
+                    ui.send_modify(|s| s.recorded = Some(status));
+                }
+                continue;
+            }
+        };
+        if let Some(gap) = gap {
+            if next_due.is_some_and(|due| frame.at < due) {
+                continue;
+            }
+            // Stay on the grid unless input paused for longer than one interval.
+            next_due = Some(match next_due {
+                Some(due) if frame.at - due < gap => due + gap,
+                _ => frame.at + gap,
+            });
+        }
+        if max_bytes.is_some_and(|max| size >= max) {
+            let _ = out.flush();
+            status.part += 1;
+            file = record_part(&path, status.part);
+            (out, size) = match open_record(&file) {
+                Ok(o) => o,
+                Err(e) => {
+                    eprintln!("Cannot open recording {}: {e}", file.display());
+                    return;
+                }
+            };
+        }
+        let t_ms = frame.at.saturating_duration_since(start).as_millis();
+        let wall_ms = unix_ms(frame.wall);
+        let line = format!(
+            "{{\"t_ms\":{t_ms},\"wall_ms\":{wall_ms},\"data\":{}}}\n",
+            frame.line
+        );
+        if let Err(e) = out.write_all(line.as_bytes()) {
+            eprintln!("Recording to {} failed: {e}", file.display());
+            return;
+        }
+        size += line.len() as u64;
+        status.frames += 1;
+        pending = true;
+    }
+    let _ = out.flush();
+}
+
+/// Write the pad codes seen this session as a [wacom.buttons] table to paste into the
+/// config, to `dcsctl-buttons-<unix ms>.toml` in the working directory.
+#[cfg(feature = "wacom")]
+fn write_button_map(s: &UiState) -> Result<PathBuf> {
+    let wall_ms = unix_ms(SystemTime::now());
+    let path = PathBuf::from(format!("dcsctl-buttons-{wall_ms}.toml"));
+    let mut codes: Vec<_> = s.pad_codes.iter().collect();
+    codes.sort_by_key(|(code, _)| **code);
+    let mut out = String::from(
+        "# Pad buttons seen by dcsctl, with the action each is mapped to now. Edit the\n\
+         # actions (up, down, left, right, select, none) and paste this into your config.\n\
+         [wacom.buttons]\n",
+    );
+    for (code, (n, act)) in codes {
+        let pressed = format!("{} x{n}", pad_code_name(*code));
+        out += &format!(
+            "\"{code}\" = {:<9} # {pressed}\n",
+            format!("\"{}\"", act.name())
+        );
+    }
+    fs::write(&path, out).with_context(|| format!("writing {}", path.display()))?;
+    Ok(path)
+}
+
+/// Write the current telemetry, merged raw fields, chart histories, flight log and the
+/// session's largest engine splits to
+/// `dcsctl-snapshot-<unix ms>.json` in the working directory.
+fn write_snapshot(s: &UiState) -> Result<PathBuf> {
+    let wall_ms = unix_ms(SystemTime::now());
+    let path = PathBuf::from(format!("dcsctl-snapshot-{wall_ms}.json"));
+    let snap = serde_json::json!({
+        "wall_ms": wall_ms,
+        "telemetry": s.last,
+        "raw": s.raw,
+        "history": {
+            "ias_ms": s.ias_hist,
+            "alt_m": s.alt_hist,
+            "mach": s.mach_hist,
+            "ps_ms": s.ps_hist,
+            "vv_ms": s.vv_hist,
+            "g": s.g_hist,
+        },
+        "flight_log": s.flight_log.events,
+        "max_engine_split": s.max_split,
+    });
+    fs::write(&path, serde_json::to_string_pretty(&snap)?)
+        .with_context(|| format!("writing {}", path.display()))?;
+    Ok(path)
+}
+
+/// Fixed-cadence chart advance for charts.sample_ms. Intervals without flight packets
+/// add nothing, same as per-packet mode when data stops.
+async fn chart_ticker(tx: watch::Sender<UiState>, cfg: Arc<Config>) {
+    let mut tick = tokio::time::interval(Duration::from_millis(cfg.charts.sample_ms));
+    tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tick.tick().await;
+        tx.send_if_modified(|s| {
+            let now = Instant::now();
+            let mut changed = false;
+            if let Some(c) = &mut s.compare
+                && let Some(sample) = c.track.chart_acc.take()
+            {
+                c.track.push_chart_sample(sample, &cfg, now);
+                changed = true;
+            }
+            // --replay samples on its own clock in apply_telemetry.
+            if s.replay_clock.is_some() {
+                return changed;
+            }
+            // Background airframes keep charting; only the shown one needs a redraw.
+            for track in s.airframes.values_mut() {
+                if let Some(sample) = track.chart_acc.take() {
+                    track.push_chart_sample(sample, &cfg, now);
+                }
+            }
+            if let Some(sample) = s.chart_acc.take() {
+                s.push_chart_sample(sample, &cfg, now);
+                changed = true;
+            }
+            changed
+        });
+    }
+}
+
+/// Rewrite `path` every tick. Goes through a temp file + rename so readers never see a
+/// half-written line.
+async fn status_line_writer(path: PathBuf, rx: watch::Receiver<UiState>) {
+    let mut tmp = path.clone().into_os_string();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut failing = false;
+    loop {
+        let line = format_status_line(&rx.borrow().last);
+        match fs::write(&tmp, line + "\n").and_then(|_| fs::rename(&tmp, &path)) {
+            Ok(()) => failing = false,
+            Err(e) if !failing => {
+                eprintln!("Status line write to {} failed: {e}", path.display());
+                failing = true;
+            }
+            Err(_) => {}
+        }
+        sleep(Duration::from_millis(TICK_MS)).await;
+    }
+}
+
+// ---------------- Runtime state ----------------
+
+/// What state.persist carries over: everything changed with keys at runtime, over the
+/// config file's values. Missing fields keep the config's.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct RuntimeState {
+    units: Option<Units>,
+    angles: Option<AngleUnit>,
+    night: bool,
+    focused: Option<Pane>,
+    fullscreen: Option<Pane>,
+    hidden: Vec<Pane>,
+    chart_selection: Option<usize>,
+    header: Option<HeaderMode>,
+    dense: Option<bool>,
+    trend: Option<bool>,
+    aoa_units: Option<bool>,
+    show_extremes: bool,
+    /// Zeroed reference altitude (`z`), m MSL.
+    alt_datum: Option<f64>,
+}
+
+impl RuntimeState {
+    fn capture(s: &UiState) -> RuntimeState {
+        // The Inputs pane is opened by key only and has no config name.
+        let pane = |p: Pane| (p != Pane::Inputs).then_some(p);
+        RuntimeState {
+            units: Some(s.units),
+            angles: Some(s.angles),
+            night: s.night,
+            focused: pane(s.focused),
+            fullscreen: s.fullscreen.and_then(pane),
+            hidden: s.hidden.iter().copied().collect(),
+            chart_selection: s.chart_selection,
+            header: Some(s.header),
+            dense: Some(s.dense),
+            trend: Some(s.trend),
+            aoa_units: Some(s.aoa_units),
+            show_extremes: s.show_extremes,
+            alt_datum: s.alt_datum,
+        }
+    }
+
+    fn apply(self, s: &mut UiState) {
+        s.units = self.units.unwrap_or(s.units);
+        s.angles = self.angles.unwrap_or(s.angles);
+        s.night = self.night;
+        s.hidden = self.hidden.into_iter().collect();
+        s.chart_selection = self.chart_selection.filter(|&i| i < CHART_ROWS.len());
+        s.header = self.header.unwrap_or(s.header);
+        s.dense = self.dense.unwrap_or(s.dense);
+        s.trend = self.trend.unwrap_or(s.trend);
+        s.aoa_units = self.aoa_units.unwrap_or(s.aoa_units);
+        s.show_extremes = self.show_extremes;
+        s.alt_datum = self.alt_datum;
+        if let Some(p) = self.focused
+            && !s.off_screen().contains(&p)
+        {
+            s.focused = p;
+        }
+        s.fullscreen = self.fullscreen;
+        // A restored view counts as chosen by hand, so airframe profiles leave it be.
+        s.manual_view = self.focused.is_some() || self.fullscreen.is_some();
+    }
+}
+
+fn state_path(cfg: &Config) -> Option<PathBuf> {
+    cfg.state
+        .file
+        .clone()
+        .or_else(|| Some(config_path()?.with_file_name("state.json")))
+}
+
+/// The saved state, or why it couldn't be used; a missing file is just None.
+fn load_state(path: &PathBuf) -> Result<Option<RuntimeState>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(serde_json::from_str(&text)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Written beside and renamed over, so a crash mid-write can't leave half a file.
+fn save_state(path: &PathBuf, s: &UiState) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(
+        &tmp,
+        serde_json::to_string_pretty(&RuntimeState::capture(s))?,
+    )?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
 
-                        let (s, us) = fmt_ts(ev.timestamp());
-                        push_log(
-                            &mut state.input_log,
-                            format!(
-                                "[{:>3}.{:06}] {:?} (code={}, ABS_MISC={}) -> {:?} ({:?} side)",
-                                s,
-                                us,
-                                KeyCode::new(code_u16),
-                                code_u16,
-                                last_abs_misc,
-                                act,
-                                side_for_log
-                            ),
-                        );
-                        let _ = tx.send(state);
-                    }
-                }
-                if !saw {
-                    sleep(Duration::from_millis(10)).await;
+// ---------------- Replay ----------------
+
+/// One --record line.
+#[derive(Deserialize)]
+struct RecLine {
+    t_ms: u64,
+    data: Value,
+}
+
+/// A recording's frames with times from its first one, so two attempts start together.
+fn read_recording(path: &PathBuf) -> Result<Vec<(u64, Value)>> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let frames: Vec<_> = text
+        .lines()
+        .filter_map(|l| serde_json::from_str::<RecLine>(l).ok())
+        .collect();
+    let t0 = frames.first().map_or(0, |f| f.t_ms);
+    Ok(frames
+        .into_iter()
+        .map(|f| (f.t_ms.saturating_sub(t0), f.data))
+        .collect())
+}
+
+/// --compare: play both recordings against one clock. A goes through the normal
+/// pipeline and drives the whole dashboard; B only feeds the chart overlay.
+async fn compare_replay(a: PathBuf, b: PathBuf, pipe: Pipeline) {
+    let flash = |msg: String| {
+        pipe.tx
+            .send_modify(|s| s.flash = Some((Instant::now(), msg)))
+    };
+    let (frames_a, frames_b) = match (read_recording(&a), read_recording(&b)) {
+        (Ok(fa), Ok(fb)) => (fa, fb),
+        (Err(e), _) | (_, Err(e)) => return flash(format!("Compare failed: {e:#}")),
+    };
+    let stem = |p: &PathBuf| {
+        p.file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    };
+    let track = UiState {
+        units: pipe.cfg.units,
+        ..UiState::default()
+    };
+    pipe.tx.send_modify(|s| {
+        s.compare = Some(Box::new(Compare {
+            names: [stem(&a), stem(&b)],
+            track,
+        }))
+    });
+    let src = a.display().to_string();
+    let start = tokio::time::Instant::now();
+    let (mut fa, mut fb) = (
+        frames_a.into_iter().peekable(),
+        frames_b.into_iter().peekable(),
+    );
+    loop {
+        // Whichever file has the earlier next frame.
+        let from_a = match (fa.peek(), fb.peek()) {
+            (Some(x), Some(y)) => x.0 <= y.0,
+            (x, _) => x.is_some(),
+        };
+        let Some((t_ms, data)) = (if from_a { fa.next() } else { fb.next() }) else {
+            break;
+        };
+        tokio::time::sleep_until(start + Duration::from_millis(t_ms)).await;
+        if from_a {
+            ingest(data.to_string().as_bytes(), &pipe, &src, Framing::Newline);
+        } else if let Ok(t) = Telemetry::deserialize(&data) {
+            pipe.tx.send_modify(|s| {
+                if let Some(c) = &mut s.compare {
+                    apply_telemetry(&mut c.track, t, data, &pipe.cfg, None);
                 }
-            }
-            Err(e) => {
-                eprintln!("Wacom read error ({}): {}", path, e);
-                sleep(Duration::from_millis(300)).await;
-            }
+            });
         }
     }
+    flash("Compare replay finished".into());
 }
 
-async fn udp_listener(bind: String, tx: watch::Sender<UiState>, rx: watch::Receiver<UiState>) {
-    let sock = match UdpSocket::bind(&bind).await {
-        Ok(s) => s,
+#[derive(Debug, Clone, Copy)]
+enum ReplayCmd {
+    Pause,
+    Faster,
+    Slower,
+    /// Jump by this many ms of recording time.
+    Seek(f64),
+}
+
+impl ReplayCmd {
+    fn for_key(c: char) -> Option<ReplayCmd> {
+        Some(match c {
+            ' ' => ReplayCmd::Pause,
+            '+' | '=' => ReplayCmd::Faster,
+            '-' => ReplayCmd::Slower,
+            ']' => ReplayCmd::Seek(REPLAY_SEEK_MS),
+            '[' => ReplayCmd::Seek(-REPLAY_SEEK_MS),
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ReplayStatus {
+    pos_ms: u64,
+    len_ms: u64,
+    speed: f64,
+    paused: bool,
+    finished: bool,
+}
+
+/// --replay: feed a recording through the normal pipeline on its own clock. Seeking back
+/// starts the telemetry over and runs the frames up to the target through at once; frames
+/// carry their recording time (`UiState::replay_clock`), so charts, extremes and the
+/// flight log come out as if played, at any speed. The end just stops.
+async fn replay(path: PathBuf, pipe: Pipeline, mut ctl: mpsc::Receiver<ReplayCmd>) {
+    let frames = match read_recording(&path) {
+        Ok(f) => f,
         Err(e) => {
-            eprintln!("Bind failed on {bind}: {e}");
-            return;
+            let msg = format!("Replay failed: {e:#}");
+            return pipe
+                .tx
+                .send_modify(|s| s.flash = Some((Instant::now(), msg)));
+        }
+    };
+    let src = path.display().to_string();
+    let len_ms = frames.last().map_or(0, |f| f.0);
+    let epoch = Instant::now();
+    let feed = |&(t_ms, ref data): &(u64, Value)| {
+        pipe.tx.send_if_modified(|s| {
+            s.replay_clock = Some(epoch + Duration::from_millis(t_ms));
+            false
+        });
+        ingest(data.to_string().as_bytes(), &pipe, &src, Framing::Newline);
+    };
+    let mut next = 0;
+    let mut speed = 1;
+    let mut paused = false;
+    // Recording time `pos` was reached at `anchor`; it advances at the speed from there.
+    let (mut pos, mut anchor) = (0.0, tokio::time::Instant::now());
+    let clock = |pos: f64, anchor: tokio::time::Instant, speed: usize, paused: bool| {
+        if paused {
+            pos
+        } else {
+            pos + anchor.elapsed().as_secs_f64() * 1000.0 * REPLAY_SPEEDS[speed]
         }
     };
-    let mut buf = vec![0u8; BUF];
     loop {
-        match sock.recv_from(&mut buf).await {
-            Ok((n, _)) => {
-                for line in std::str::from_utf8(&buf[..n]).unwrap_or("").split('\n') {
-                    let line = line.trim();
-                    if line.is_empty() {
-                        continue;
-                    }
-                    if let Ok(t) = serde_json::from_str::<Telemetry>(line) {
-                        let mut state = rx.borrow().clone();
-                        push_hist(&mut state.ias_hist, t.ias_ms.unwrap_or(0.0), HISTORY);
-                        push_hist(&mut state.alt_hist, t.alt_msl.unwrap_or(0.0), HISTORY);
-                        state.last = t;
-                        let _ = tx.send(state);
+        let finished = next >= frames.len();
+        let at = clock(pos, anchor, speed, paused).min(len_ms as f64);
+        pipe.tx.send_modify(|s| {
+            s.replay = Some(ReplayStatus {
+                pos_ms: at as u64,
+                len_ms,
+                speed: REPLAY_SPEEDS[speed],
+                paused,
+                finished,
+            })
+        });
+        let due = frames.get(next).filter(|_| !paused).map(|&(t, _)| {
+            let wait_ms = (t as f64 - pos).max(0.0) / REPLAY_SPEEDS[speed];
+            anchor + Duration::from_secs_f64(wait_ms / 1000.0)
+        });
+        tokio::select! {
+            cmd = ctl.recv() => {
+                let Some(cmd) = cmd else {
+                    break;
+                };
+                // Re-anchor at the current position before changing how time runs.
+                (pos, anchor) = (at, tokio::time::Instant::now());
+                match cmd {
+                    ReplayCmd::Pause => paused = !paused,
+                    ReplayCmd::Faster => speed = (speed + 1).min(REPLAY_SPEEDS.len() - 1),
+                    ReplayCmd::Slower => speed = speed.saturating_sub(1),
+                    ReplayCmd::Seek(by) => {
+                        let target = (at + by).clamp(0.0, len_ms as f64);
+                        if target < at {
+                            pipe.tx.send_modify(UiState::rewind);
+                            next = 0;
+                        }
+                        while let Some(frame) = frames.get(next).filter(|f| f.0 as f64 <= target) {
+                            feed(frame);
+                            next += 1;
+                        }
+                        pos = target;
                     }
                 }
             }
-            Err(e) => {
-                eprintln!("UDP recv error: {e}");
-                sleep(Duration::from_millis(200)).await;
+            _ = tokio::time::sleep_until(due.unwrap_or_else(tokio::time::Instant::now)), if due.is_some() => {
+                feed(&frames[next]);
+                next += 1;
+            }
+        }
+    }
+}
+
+// ---------------- Headless ----------------
+
+async fn run_headless(mut rx: watch::Receiver<UiState>, interval: Option<Duration>) -> Result<()> {
+    let mut summary = Summary::default();
+    let mut ticker = tokio::time::interval(interval.unwrap_or(Duration::from_secs(1)));
+    ticker.tick().await; // first tick fires immediately
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            changed = rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let t = rx.borrow_and_update().last.clone();
+                match interval {
+                    Some(_) => summary.add(&t),
+                    None => println!("{}", format_status_line(&t)),
+                }
+            }
+            _ = ticker.tick(), if interval.is_some() => {
+                let done = std::mem::take(&mut summary);
+                if done.samples > 0 {
+                    println!("{}", done.line());
+                }
             }
         }
     }
+    Ok(())
+}
+
+/// Running average/max of one value; `None` samples are ignored.
+#[derive(Debug, Clone, Copy, Default)]
+struct Stat {
+    sum: f64,
+    n: u32,
+    min: f64,
+    max: f64,
+}
+
+impl Stat {
+    fn add(&mut self, v: Option<f64>) {
+        let Some(v) = v.filter(|v| v.is_finite()) else {
+            return;
+        };
+        if self.n == 0 {
+            (self.min, self.max) = (v, v);
+        } else {
+            self.min = self.min.min(v);
+            self.max = self.max.max(v);
+        }
+        self.sum += v;
+        self.n += 1;
+    }
+
+    fn avg(&self) -> Option<f64> {
+        (self.n > 0).then(|| self.sum / self.n as f64)
+    }
+}
+
+/// Telemetry aggregated between two headless summary lines.
+#[derive(Debug, Clone, Default)]
+struct Summary {
+    samples: u32,
+    ias_kt: Stat,
+    alt_m: Stat,
+    mach: Stat,
+    g: Stat,
+    rpm: Stat,
+}
+
+impl Summary {
+    fn add(&mut self, t: &Telemetry) {
+        self.samples += 1;
+        self.ias_kt.add(t.ias_ms.map(|v| v * 1.943_844));
+        self.alt_m.add(t.alt_msl);
+        self.mach.add(t.mach);
+        self.g.add(t.accel.as_ref().and_then(|a| a.y));
+        let rpm = t.engine.as_ref().and_then(|e| e.rpm.as_ref());
+        self.rpm.add(rpm.and_then(|p| p.L));
+    }
+
+    fn line(&self) -> String {
+        let avg_max = |s: &Stat, prec: usize| match s.avg() {
+            Some(avg) => format!("{avg:.prec$}/{:.prec$}", s.max),
+            None => "---".into(),
+        };
+        let g_min = self.g.avg().map(|_| format!("{:.1}", self.g.min));
+        format!(
+            "n={}  IAS {} kt  ALT {} m  M {}  G {} (min {})  RPM {} %",
+            self.samples,
+            avg_max(&self.ias_kt, 0),
+            avg_max(&self.alt_m, 0),
+            avg_max(&self.mach, 2),
+            avg_max(&self.g, 1),
+            g_min.as_deref().unwrap_or("---"),
+            avg_max(&self.rpm, 1),
+        )
+    }
 }
 
 // ---------------- Wacom / evdev ----------------
@@ -402,16 +4566,17 @@ fn open_wacom_from_env() -> Option<(String, Device)> {
     if let Ok(p) = std::env::var("WACOM_EVENT") {
         match Device::open(&p) {
             Ok(d) => {
-                eprintln!("Using WACOM_EVENT {}", p);
+                tracing::debug!("Using WACOM_EVENT {p}");
                 return Some((p, d));
             }
-            Err(e) => eprintln!("WACOM_EVENT={} open failed: {}", p, e),
+            Err(e) => tracing::warn!("WACOM_EVENT={p} open failed: {e}"),
         }
     }
     None
 }
 
-/// Try /dev/input/by-id first (stable symlinks), then /dev/input
+/// Try /dev/input/by-id first (stable symlinks), then /dev/input. Doesn't wait for a
+/// pad to appear; each candidate tried is logged at debug level (`-v`).
 #[cfg(feature = "wacom")]
 fn find_wacom_pad() -> Option<(String, Device)> {
     if let Some(t) = open_wacom_from_env() {
@@ -432,71 +4597,159 @@ fn find_wacom_pad() -> Option<(String, Device)> {
                 {
                     match Device::open(&tgt) {
                         Ok(d) => {
-                            eprintln!("Wacom pad (by-id): {} -> {}", name, tgt.display());
+                            tracing::debug!("Wacom pad (by-id): {name} -> {}", tgt.display());
                             return Some((tgt.display().to_string(), d));
                         }
-                        Err(e) => eprintln!("Found {} but open failed: {}", tgt.display(), e),
+                        Err(e) => {
+                            tracing::debug!("Found {} but open failed: {e}", tgt.display())
+                        }
                     }
                 }
             }
         }
     }
 
-    if let Ok(entries) = fs::read_dir("/dev/input") {
-        for ent in entries.flatten() {
-            let p = ent.path();
-            let fname = p
-                .file_name()
-                .map(|s| s.to_string_lossy())
-                .unwrap_or_default();
-            if !fname.starts_with("event") {
-                continue;
-            }
-            match Device::open(&p) {
-                Ok(d) => {
-                    let n = d.name().unwrap_or("");
-                    if n.contains("Wacom") && n.contains("Pad") {
-                        eprintln!("Wacom pad: {} ({})", n, p.display());
-                        return Some((p.display().to_string(), d));
-                    }
+    for (p, dev) in input_devices() {
+        match dev {
+            Ok(d) => {
+                let n = d.name().unwrap_or("");
+                if is_pad_name(n) {
+                    tracing::debug!("Wacom pad: {n} ({})", p.display());
+                    return Some((p.display().to_string(), d));
                 }
-                Err(e) => eprintln!("Skip {} (open failed): {}", p.display(), e),
             }
+            Err(e) => tracing::debug!("Skip {} (open failed): {e}", p.display()),
         }
     }
     None
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Every /dev/input/event* node, by event number, opened (or why not).
+#[cfg(feature = "wacom")]
+fn input_devices() -> Vec<(PathBuf, io::Result<Device>)> {
+    let mut paths: Vec<PathBuf> = match fs::read_dir("/dev/input") {
+        Ok(entries) => entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with("event"))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    let num = |p: &PathBuf| {
+        p.file_name()
+            .and_then(|n| n.to_string_lossy()["event".len()..].parse::<u32>().ok())
+    };
+    paths.sort_by_key(num);
+    paths
+        .into_iter()
+        .map(|p| {
+            let dev = Device::open(&p);
+            (p, dev)
+        })
+        .collect()
+}
+
+/// Device names the pad goes by (the pen and touch parts of a tablet don't match).
+#[cfg(feature = "wacom")]
+fn is_pad_name(name: &str) -> bool {
+    name.contains("Wacom") && name.contains("Pad")
+}
+
+/// `--list-devices`: each input device's path, name and event types, marking pads.
+#[cfg(feature = "wacom")]
+fn list_devices() -> Result<()> {
+    let devices = input_devices();
+    if devices.is_empty() {
+        println!("No /dev/input/event* devices found.");
+        return Ok(());
+    }
+    for (p, dev) in devices {
+        match dev {
+            Ok(d) => {
+                let name = d.name().unwrap_or("?");
+                let caps: Vec<String> = d
+                    .supported_events()
+                    .iter()
+                    .filter(|t| *t != EventType::SYNCHRONIZATION)
+                    .map(|t| format!("{t:?}").to_lowercase())
+                    .collect();
+                let keys = d.supported_keys().map_or(0, |k| k.iter().count());
+                let pad = if is_pad_name(name) { "  <- pad" } else { "" };
+                println!(
+                    "{}  {name}  [{}; {keys} keys]{pad}",
+                    p.display(),
+                    caps.join(", ")
+                );
+            }
+            Err(e) => println!("{}  (can't open: {e})", p.display()),
+        }
+    }
+    println!("Use one with WACOM_EVENT=/dev/input/eventN.");
+    Ok(())
+}
+
+/// Config names: "up", "down", "left", "right", "select", "none".
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(not(feature = "wacom"), allow(dead_code))]
 enum PadAction {
     Up,
     Down,
     Left,
     Right,
     Select, // toggle fullscreen
+    #[serde(rename = "none")]
     Unknown,
 }
 
+impl PadAction {
+    #[cfg_attr(not(feature = "wacom"), allow(dead_code))]
+    fn name(self) -> &'static str {
+        match self {
+            PadAction::Up => "up",
+            PadAction::Down => "down",
+            PadAction::Left => "left",
+            PadAction::Right => "right",
+            PadAction::Select => "select",
+            PadAction::Unknown => "none",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "wacom"), allow(dead_code))]
 enum Side {
     Left,
     Right,
 }
 
 /// (For logging only) ABS_MISC (code 40) often flips between 0 and >0 when you touch/use a side.
+/// Values in (-deadzone, deadzone] give None, keeping the last side; 0 disables the band.
 #[cfg(feature = "wacom")]
-fn side_from_abs(code_u16: u16, val: i32) -> Option<Side> {
+fn side_from_abs(code_u16: u16, val: i32, deadzone: i32) -> Option<Side> {
     match code_u16 {
         40 /* ABS_MISC */ => {
-            if val > 0 { Some(Side::Right) } else { Some(Side::Left) }
+            if val > deadzone {
+                Some(Side::Right)
+            } else if val <= -deadzone {
+                Some(Side::Left)
+            } else {
+                None
+            }
         }
         _ => None,
     }
 }
 
-/// Map by raw button code (works for both sides).
+/// Map by raw button code (works for both sides); wacom.buttons wins.
 #[cfg(feature = "wacom")]
-fn map_btn_code(code_u16: u16) -> PadAction {
+fn map_btn_code(code_u16: u16, buttons: &BTreeMap<String, PadAction>) -> PadAction {
+    if let Some(&act) = buttons.get(&code_u16.to_string()) {
+        return act;
+    }
     match code_u16 {
         // LEFT PAD
         264 => PadAction::Up,     // Top
@@ -526,32 +4779,48 @@ fn side_from_code(code_u16: u16) -> Option<Side> {
     }
 }
 
-fn move_focus(focused: Pane, dir: PadAction) -> Pane {
+/// Up/Down like `step_focus`, but passes over hidden panes. Moving up into the top row lands
+/// on its first visible pane; if nothing visible lies that way, focus stays put.
+fn move_focus(focused: Pane, dir: PadAction, hidden: &HashSet<Pane>) -> Pane {
+    let mut p = focused;
+    for _ in 0..PANE_COUNT {
+        let next = step_focus(p, dir);
+        if next == p {
+            break;
+        }
+        p = next;
+        if !hidden.contains(&p) {
+            return p;
+        }
+        if matches!(dir, PadAction::Up) && TOP_PANES.contains(&p) {
+            if let Some(&v) = TOP_PANES.iter().find(|q| !hidden.contains(q)) {
+                return v;
+            }
+            break;
+        }
+    }
+    focused
+}
+
+fn step_focus(focused: Pane, dir: PadAction) -> Pane {
     use Pane::*;
     match dir {
-        PadAction::Left => match focused {
-            Flight => Systems, // wrap within the top row of 3
-            Att => Flight,
-            Systems => Att,
-            IasChart | AltChart => focused, // left/right do nothing on charts
-            Inputs => Flight,               // defensive: if ever focused, bounce to visible
-        },
-        PadAction::Right => match focused {
-            Flight => Att,
-            Att => Systems,
-            Systems => Flight, // wrap
-            IasChart | AltChart => focused,
-            Inputs => Flight, // defensive
-        },
         PadAction::Up => match focused {
             IasChart => Flight,
             AltChart => IasChart,
+            MachChart => AltChart,
+            PsChart => MachChart,
+            VvChart => PsChart,
+            GChart => VvChart,
             other => other,
         },
         PadAction::Down => match focused {
-            Flight | Att | Systems => IasChart,
+            Flight | Att | Systems | Computed | Stats => IasChart,
             IasChart => AltChart,
-            AltChart => AltChart,
+            AltChart => MachChart,
+            MachChart => PsChart,
+            PsChart => VvChart,
+            VvChart | GChart => GChart,
             Inputs => IasChart, // defensive
         },
         _ => focused,
@@ -559,12 +4828,16 @@ fn move_focus(focused: Pane, dir: PadAction) -> Pane {
 }
 
 #[cfg(feature = "wacom")]
-async fn wacom_listener(tx: watch::Sender<UiState>, rx: watch::Receiver<UiState>) {
+async fn wacom_listener(
+    tx: watch::Sender<UiState>,
+    rx: watch::Receiver<UiState>,
+    wacom: WacomConfig,
+) {
     let (path, mut dev) = loop {
         match find_wacom_pad() {
             Some((p, d)) => break (p, d),
             None => {
-                eprintln!("No readable Wacom pad yet; retrying…");
+                tracing::debug!("No readable Wacom pad yet; retrying…");
                 sleep(Duration::from_millis(1500)).await;
             }
         }
@@ -583,19 +4856,20 @@ async fn wacom_listener(tx: watch::Sender<UiState>, rx: watch::Receiver<UiState>
                     saw = true;
 
                     if ev.event_type() == EventType::ABSOLUTE {
-                        if let Some(s) = side_from_abs(ev.code(), ev.value()) {
+                        if let Some(s) = side_from_abs(ev.code(), ev.value(), wacom.abs_deadzone) {
                             last_side_hint = s;
                             last_abs_at = Instant::now();
                         }
                         if ev.code() == 40 {
                             last_abs_misc = ev.value();
                         }
+                        tx.send_modify(|s| s.note_pad_abs(ev.code(), ev.value()));
                     }
 
                     // Only react on key DOWN
                     if ev.event_type() == EventType::KEY && ev.value() == 1 {
                         let code_u16 = ev.code();
-                        let act = map_btn_code(code_u16);
+                        let act = map_btn_code(code_u16, &wacom.buttons);
 
                         let mut state = rx.borrow().clone();
                         match act {
@@ -610,10 +4884,11 @@ async fn wacom_listener(tx: watch::Sender<UiState>, rx: watch::Receiver<UiState>
                             | PadAction::Down
                             | PadAction::Left
                             | PadAction::Right => {
-                                state.focused = move_focus(state.focused, act);
+                                state.focused = state.focus_toward(act);
                             }
                             PadAction::Unknown => {}
                         }
+                        state.manual_view |= !matches!(act, PadAction::Unknown);
 
                         // Prefer inferring side from code; fall back to recent ABS hint
                         let side_for_log = side_from_code(code_u16)
@@ -640,6 +4915,7 @@ async fn wacom_listener(tx: watch::Sender<UiState>, rx: watch::Receiver<UiState>
                                 side_for_log
                             ),
                         );
+                        state.note_pad_code(code_u16, act);
                         let _ = tx.send(state);
                     }
                 }
@@ -657,36 +4933,278 @@ async fn wacom_listener(tx: watch::Sender<UiState>, rx: watch::Receiver<UiState>
 
 // ---------------- TUI ----------------
 
-async fn run_tui(rx: watch::Receiver<UiState>) -> Result<()> {
+/// `reconnect` is None when every input is a datagram socket, with nothing to reopen.
+async fn run_tui(
+    tx: watch::Sender<UiState>,
+    mut rx: watch::Receiver<UiState>,
+    cfg: Arc<Config>,
+    reconnect: Option<watch::Sender<()>>,
+    replay: Option<mpsc::Sender<ReplayCmd>>,
+    tick_ms: u64,
+) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    // Put the terminal back before the panic message prints, or it lands in the
+    // alternate screen and vanishes with it.
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        default_hook(info);
+    }));
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     let mut last_redraw = Instant::now();
+    let night_cfg = Config {
+        theme: Theme::night(),
+        ..(*cfg).clone()
+    };
+
+    let started = Instant::now();
+    let mut force_redraw = false;
+    // redraw.on_change: the state changed since the last frame.
+    let mut dirty = false;
+    let mut last_bell: Option<Instant> = None;
+    let mut last_key = started;
+    // Rendered refresh_ms panes and when each was last redrawn.
+    let mut held: HashMap<Pane, (Instant, Arc<Mutex<HeldPane>>)> = HashMap::new();
 
     'ui: loop {
         while event::poll(Duration::from_millis(0))? {
+            let ev = event::read()?;
+            // Keys and resizes show up immediately, even while idle and in slow panes.
+            force_redraw = true;
+            held.clear();
             if let Event::Key(KeyEvent {
                 code, modifiers, ..
-            }) = event::read()?
+            }) = ev
             {
+                last_key = Instant::now();
+                // The first key only wakes the screensaver, or dismisses the splash.
+                if rx.borrow().dimmed {
+                    tx.send_modify(|s| s.dimmed = false);
+                    continue;
+                }
+                let splash = rx.borrow().splash.as_ref().map(|(at, _)| at.elapsed());
+                if splash.is_some_and(|d| d.as_secs_f64() < cfg.splash.secs) {
+                    tx.send_modify(|s| s.splash = None);
+                    continue;
+                }
+                // Pad-equivalent navigation, logged with the pad events.
+                let nav = match code {
+                    TermKeyCode::Up => Some(PadAction::Up),
+                    TermKeyCode::Down => Some(PadAction::Down),
+                    TermKeyCode::Left => Some(PadAction::Left),
+                    TermKeyCode::Right => Some(PadAction::Right),
+                    TermKeyCode::Enter => Some(PadAction::Select),
+                    TermKeyCode::Char('f') if modifiers == KeyModifiers::NONE => {
+                        Some(PadAction::Select)
+                    }
+                    _ => None,
+                };
+                if let Some(act) = nav {
+                    tx.send_modify(|s| {
+                        s.pad_action(act);
+                        let line = format!("[key] {code} -> {act:?} (focus {:?})", s.focused);
+                        push_log(&mut s.input_log, line);
+                    });
+                    continue;
+                }
+                if matches!(code, TermKeyCode::Tab | TermKeyCode::BackTab) {
+                    tx.send_modify(|s| {
+                        s.cycle_focus(code == TermKeyCode::BackTab);
+                        let line = format!("[key] {code} -> focus {:?}", s.focused);
+                        push_log(&mut s.input_log, line);
+                    });
+                    continue;
+                }
                 match (code, modifiers) {
                     (TermKeyCode::Char('c'), KeyModifiers::CONTROL)
                     | (TermKeyCode::Char('q'), KeyModifiers::NONE)
                     | (TermKeyCode::Esc, _) => break 'ui,
+                    (TermKeyCode::Char('p'), KeyModifiers::NONE) => {
+                        tx.send_modify(UiState::toggle_pin)
+                    }
+                    (TermKeyCode::Char('n'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.night = !s.night)
+                    }
+                    (TermKeyCode::Char('a'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.angles = s.angles.toggle())
+                    }
+                    (TermKeyCode::Char('A'), _) => tx.send_modify(|s| s.aoa_units = !s.aoa_units),
+                    (TermKeyCode::Char('u'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.units = s.units.next())
+                    }
+                    (TermKeyCode::Char('c'), KeyModifiers::NONE) => tx.send_modify(|s| {
+                        s.rx_stats = RxStats::default();
+                        s.seq = SeqStats::default();
+                    }),
+                    (TermKeyCode::Char('x'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.show_extremes = !s.show_extremes)
+                    }
+                    (TermKeyCode::Char('X'), _) => {
+                        tx.send_modify(|s| s.extremes = Extremes::default())
+                    }
+                    (TermKeyCode::Char('v'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.trend = !s.trend)
+                    }
+                    (TermKeyCode::Char('l'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.show_log = !s.show_log)
+                    }
+                    (TermKeyCode::Char('L'), _) => tx.send_modify(|s| s.flight_log.clear()),
+                    (TermKeyCode::Char('D'), _) => tx.send_modify(|s| s.dense = !s.dense),
+                    (TermKeyCode::Char('H'), _) => tx.send_modify(|s| s.header = s.header.next()),
+                    (TermKeyCode::Char('r'), KeyModifiers::NONE) => {
+                        let msg = match &reconnect {
+                            Some(r) => {
+                                r.send_replace(());
+                                "Reconnecting inputs"
+                            }
+                            None => "Nothing to reconnect (datagram inputs only)",
+                        };
+                        tx.send_modify(|s| s.flash = Some((Instant::now(), msg.into())))
+                    }
+                    (TermKeyCode::Char('d'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.show_diag = !s.show_diag)
+                    }
+                    (TermKeyCode::Char('h'), KeyModifiers::NONE) | (TermKeyCode::Home, _) => tx
+                        .send_modify(|s| {
+                            s.go_home(cfg.home);
+                            s.manual_view = true;
+                        }),
+                    #[cfg(feature = "wacom")]
+                    (TermKeyCode::Char('w'), KeyModifiers::NONE) => tx.send_modify(|s| {
+                        s.fullscreen = match s.fullscreen {
+                            Some(Pane::Inputs) => None,
+                            _ => Some(Pane::Inputs),
+                        };
+                        s.manual_view = true;
+                    }),
+                    #[cfg(feature = "wacom")]
+                    (TermKeyCode::Char('W'), _) => tx.send_modify(|s| {
+                        s.pad_codes.clear();
+                        s.pad_abs.clear();
+                    }),
+                    #[cfg(feature = "wacom")]
+                    (TermKeyCode::Char('E'), _) => {
+                        let msg = match write_button_map(&rx.borrow()) {
+                            Ok(path) => format!("Saved {}", path.display()),
+                            Err(e) => format!("Button map export failed: {e:#}"),
+                        };
+                        tx.send_modify(|s| s.flash = Some((Instant::now(), msg)))
+                    }
+                    (TermKeyCode::Char('z'), KeyModifiers::NONE) => {
+                        tx.send_modify(|s| s.alt_datum = s.last.alt_msl.or(s.alt_datum))
+                    }
+                    (TermKeyCode::Char('Z'), _) => tx.send_modify(|s| s.alt_datum = None),
+                    (TermKeyCode::Char('t'), KeyModifiers::NONE) => tx.send_modify(|s| {
+                        s.cycle_chart();
+                        s.manual_view = true;
+                    }),
+                    (TermKeyCode::Char('m'), KeyModifiers::NONE) => {
+                        tx.send_modify(UiState::cycle_airframe)
+                    }
+                    (TermKeyCode::Char('s'), KeyModifiers::NONE) => {
+                        let msg = match write_snapshot(&rx.borrow()) {
+                            Ok(path) => format!("Saved {}", path.display()),
+                            Err(e) => format!("Snapshot failed: {e:#}"),
+                        };
+                        tx.send_modify(|s| s.flash = Some((Instant::now(), msg)))
+                    }
+                    (TermKeyCode::Char(c), m) => {
+                        if let Some(ctl) = &replay
+                            && let Some(cmd) = ReplayCmd::for_key(c)
+                        {
+                            let _ = ctl.try_send(cmd);
+                        } else if let Some(pane) = hide_key(c, m) {
+                            tx.send_modify(|s| {
+                                s.toggle_hidden(pane);
+                                s.manual_view = true;
+                            })
+                        }
+                    }
                     _ => {}
                 }
             }
         }
 
-        if last_redraw.elapsed() >= Duration::from_millis(TICK_MS) {
-            let state = rx.borrow().clone();
-            terminal.draw(|f| draw(f, &state))?;
+        // Back off while nothing arrives; the first packet restores the full rate.
+        let quiet = rx.borrow().seen.packet.unwrap_or(started).elapsed();
+        let idle = cfg.idle.after_secs > 0.0 && quiet.as_secs_f64() >= cfg.idle.after_secs;
+        let saver = cfg.screensaver.after_secs;
+        let dim = saver > 0.0 && quiet.min(last_key.elapsed()).as_secs_f64() >= saver;
+        if dim != rx.borrow().dimmed {
+            // Waking can't wait for the idle redraw interval.
+            tx.send_modify(|s| s.dimmed = dim);
+            force_redraw = true;
+        }
+        let (redraw_ms, poll_ms) = if idle {
+            (cfg.idle.redraw_ms.max(tick_ms), IDLE_POLL_MS)
+        } else {
+            (tick_ms, 10)
+        };
+
+        dirty |= rx.has_changed().unwrap_or(false);
+        let since = last_redraw.elapsed();
+        let due = if cfg.redraw.on_change {
+            // The idle-rate heartbeat keeps time-based bits (stale marks, flashes) moving.
+            dirty && since >= Duration::from_millis(cfg.redraw.min_ms)
+                || since >= Duration::from_millis(cfg.idle.redraw_ms.max(tick_ms))
+        } else {
+            since >= Duration::from_millis(redraw_ms)
+        };
+        if force_redraw || due {
+            let mut state = rx.borrow_and_update().clone();
+            dirty = false;
+            for (&pane, &ms) in &cfg.refresh_ms {
+                let (at, cache) = held.entry(pane).or_insert_with(|| {
+                    let cache = HeldPane {
+                        due: true,
+                        cells: None,
+                    };
+                    (Instant::now(), Arc::new(Mutex::new(cache)))
+                });
+                if at.elapsed() >= Duration::from_millis(ms) {
+                    *at = Instant::now();
+                    if let Ok(mut c) = cache.lock() {
+                        c.due = true;
+                    }
+                }
+            }
+            state.held = held.iter().map(|(&p, (_, h))| (p, h.clone())).collect();
+            let cfg = if state.night { &night_cfg } else { &*cfg };
+            terminal.draw(|f| {
+                draw(f, &state, cfg);
+                if state.dimmed {
+                    let area = f.area();
+                    dim_area(f.buffer_mut(), area, cfg.screensaver.level, cfg);
+                }
+            })?;
             last_redraw = Instant::now();
+            force_redraw = false;
+        }
+
+        if cfg.alerts.bell
+            && rx.borrow().warnings.over_g
+            && last_bell.is_none_or(|t| t.elapsed() >= Duration::from_millis(BELL_INTERVAL_MS))
+        {
+            let out = terminal.backend_mut();
+            out.write_all(b"\x07")?;
+            Write::flush(out)?;
+            last_bell = Some(Instant::now());
         }
 
-        sleep(Duration::from_millis(10)).await;
+        // Keys are polled, so wake for them too; a pending change waits out min_ms.
+        let poll = sleep(Duration::from_millis(poll_ms));
+        if cfg.redraw.on_change && !dirty {
+            tokio::select! {
+                r = rx.changed() => dirty = r.is_ok(),
+                _ = poll => {}
+            }
+        } else {
+            poll.await;
+        }
     }
 
     disable_raw_mode()?;
@@ -695,102 +5213,571 @@ async fn run_tui(rx: watch::Receiver<UiState>) -> Result<()> {
     Ok(())
 }
 
-fn draw(f: &mut Frame, s: &UiState) {
+/// Scale the colors in `area` toward black by `level` (screensaver, stale link). The
+/// terminal's default foreground has no known RGB, so it dims from light gray; default
+/// backgrounds stay as they are. With no_color only the DIM attribute is available.
+fn dim_area(buf: &mut Buffer, area: Rect, level: f64, cfg: &Config) {
+    let scale = |(r, g, b): (u8, u8, u8)| {
+        let f = |v: u8| (v as f64 * level).round() as u8;
+        Color::Rgb(f(r), f(g), f(b))
+    };
+    for pos in area.intersection(buf.area).positions() {
+        let cell = &mut buf[pos];
+        if cfg.no_color {
+            cell.modifier.insert(Modifier::DIM);
+            continue;
+        }
+        cell.fg = scale(rgb(cell.fg).unwrap_or((229, 229, 229)));
+        if let Some(bg) = rgb(cell.bg) {
+            cell.bg = scale(bg);
+        }
+    }
+}
+
+/// Shift+1..9 toggles a pane. Terminals usually deliver the US-layout symbol (`!`, `@`,
+/// ...); with keyboard enhancement it arrives as the digit plus SHIFT.
+fn hide_key(c: char, m: KeyModifiers) -> Option<Pane> {
+    let n = if m == KeyModifiers::SHIFT && c.is_ascii_digit() {
+        c.to_digit(10)? as usize
+    } else if m.difference(KeyModifiers::SHIFT).is_empty() {
+        ['!', '@', '#', '$', '%', '^', '&', '*', '(']
+            .iter()
+            .position(|&s| s == c)?
+            + 1
+    } else {
+        return None;
+    };
+    LAYOUT_PANES.get(n.checked_sub(1)?).copied()
+}
+
+fn draw(f: &mut Frame, s: &UiState, cfg: &Config) {
+    f.render_widget(Block::default().style(cfg.style(cfg.theme.base)), f.area());
+    if s.dense {
+        draw_dense(f, s, cfg);
+        draw_overlays(f, s, cfg);
+        return;
+    }
     // header area
+    let header = header_line(s, cfg);
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),
+            Constraint::Length(header.as_ref().map_or(0, |(_, h)| *h)),
             Constraint::Length(12),
             Constraint::Min(6),
             Constraint::Min(6),
+            Constraint::Min(6),
         ])
         .split(f.area());
 
     // Fullscreen: only draw header + focused pane stretched
     if let Some(fs) = s.fullscreen {
-        f.render_widget(header_line(&s.last), layout[0]);
+        if let Some((header, _)) = header {
+            f.render_widget(header, layout[0]);
+        }
+        // Everything under the header, whatever the row constraints left over.
         let fs_area = Rect {
-            x: layout[1].x,
-            y: layout[1].y,
-            width: layout[1].width,
-            height: layout[1].height + layout[2].height + layout[3].height,
-        };
-        draw_one_pane(f, s, fs, fs_area, true);
+            y: layout[0].bottom(),
+            height: f.area().bottom().saturating_sub(layout[0].bottom()),
+            ..f.area()
+        }
+        .intersection(f.area());
+        draw_one_pane(f, s, cfg, fs, fs_area, true);
+        dim_stale(f, s, cfg, layout[0].height);
+        draw_overlays(f, s, cfg);
+        return;
+    }
+
+    // normal layout
+    if let Some((header, _)) = header {
+        f.render_widget(header, layout[0]);
+    }
+
+    // Hidden panes give their space to the rest: top-row panes share the row width,
+    // chart rows share the height below it.
+    let top = s.top_shown();
+    let charts = s.chart_slots();
+    let body = Rect {
+        height: f.area().height.saturating_sub(layout[0].height),
+        ..layout[1]
+    };
+    let mut rows = vec![];
+    if !top.is_empty() {
+        rows.push(if charts.is_empty() {
+            Constraint::Min(0)
+        } else {
+            Constraint::Length(12)
+        });
+    }
+    let primary = cfg.charts.primary.map(ChartName::pane);
+    rows.extend(charts.iter().map(|&p| match primary {
+        None => Constraint::Min(6),
+        Some(q) => Constraint::Fill(if p == q { 2 } else { 1 }),
+    }));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(rows)
+        .split(body);
+
+    let chart_areas = if top.is_empty() {
+        &rows[..]
+    } else {
+        let stats_row = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(top.iter().map(|p| match &cfg.layout.top_widths {
+                // Fill keeps the configured proportions when a pane is hidden.
+                Some(w) => {
+                    let i = cfg.layout.top.iter().position(|q| q == p).unwrap_or(0);
+                    Constraint::Fill(w[i])
+                }
+                None => Constraint::Ratio(1, top.len() as u32),
+            }))
+            .split(rows[0]);
+        for (&pane, &area) in top.iter().zip(stats_row.iter()) {
+            draw_one_pane(f, s, cfg, pane, area, false);
+        }
+        &rows[1..]
+    };
+
+    // charts (full width blocks)
+    for (&pane, &area) in charts.iter().zip(chart_areas) {
+        draw_one_pane(f, s, cfg, pane, area, false);
+    }
+    dim_stale(f, s, cfg, layout[0].height);
+    draw_overlays(f, s, cfg);
+}
+
+/// Dense layout: the key numbers in a borderless grid, as many per row as fit, for
+/// small screens. Active warnings take the first line.
+fn draw_dense(f: &mut Frame, s: &UiState, cfg: &Config) {
+    let t = &s.last;
+    let num = |v: Option<f64>, prec: usize| v.map_or("---".into(), |x| format!("{x:.prec$}"));
+    let g = t.accel.as_ref().and_then(|a| a.y).and(s.smooth_g.get());
+    let g_style = match (g, cfg.limits_for(t.name.as_deref()).g_max) {
+        (Some(g), Some(l)) => {
+            cfg.style(Style::default().fg(gradient(&cfg.colors.g_gradient, g / l)))
+        }
+        _ => Style::default(),
+    };
+    let gear = match t.mech.as_ref().and_then(|m| m.gear) {
+        Some(p) if p >= 1.0 - MECH_DEADBAND => "DN".into(),
+        Some(p) if p <= MECH_DEADBAND => "UP".into(),
+        Some(p) => format!("{:.0}%", p * 100.0),
+        None => "---".into(),
+    };
+    let flight = &cfg.colors.flight;
+    let cells = [
+        (
+            "IAS",
+            format!(
+                "{} {}",
+                num(t.ias_ms.map(|v| v * s.units.speed_scale()), 0),
+                s.units.speed_unit()
+            ),
+            cfg.fg(flight.ias),
+        ),
+        (
+            "ALT",
+            t.alt_msl.map_or("---".into(), |m| fmt_alt(m, s.units)),
+            cfg.fg(flight.alt),
+        ),
+        ("M", num(t.mach, 2), cfg.fg(flight.mach)),
+        (
+            "AoA",
+            t.aoa_rad
+                .and(s.smooth_aoa.get())
+                .map_or("---".into(), |a| s.angles.fmt(a).trim().to_string()),
+            Style::default(),
+        ),
+        ("G", num(g, 1), g_style),
+        ("GEAR", gear, Style::default()),
+    ];
+    let cell_w = cells
+        .iter()
+        .map(|(l, v, _)| l.len() + 1 + v.chars().count() + 2)
+        .max()
+        .unwrap_or(1);
+    let per_row = (f.area().width as usize / cell_w).max(1);
+    let mut lines = vec![];
+    let mut warnings = stale_spans(s, cfg);
+    warnings.extend(warning_spans(&s.warnings, cfg));
+    if !warnings.is_empty() {
+        lines.push(Line::from(warnings));
+    }
+    for row in cells.chunks(per_row) {
+        let mut spans = vec![];
+        for (label, value, style) in row {
+            let pad = cell_w - label.len() - 1 - value.chars().count();
+            spans.push(Span::raw(format!("{label} ")));
+            spans.push(Span::styled(value.clone(), *style));
+            spans.push(Span::raw(" ".repeat(pad)));
+        }
+        lines.push(Line::from(spans));
+    }
+    let top = lines.len().min(1) as u16;
+    f.render_widget(Paragraph::new(lines), f.area());
+    dim_stale(f, s, cfg, top);
+}
+
+/// Dim everything from row `top` down while the link is stale, leaving the header.
+fn dim_stale(f: &mut Frame, s: &UiState, cfg: &Config, top: u16) {
+    if s.link_stale(cfg).is_none() {
         return;
     }
+    let area = f.area();
+    let below = Rect {
+        y: area.y + top,
+        height: area.height.saturating_sub(top),
+        ..area
+    };
+    dim_area(f.buffer_mut(), below, cfg.stale.level, cfg);
+}
+
+/// Popups toggled by keys, drawn over whatever layout is showing.
+fn draw_overlays(f: &mut Frame, s: &UiState, cfg: &Config) {
+    if s.show_log {
+        draw_flight_log(f, s, cfg);
+    }
+    if s.show_diag {
+        draw_diagnostics(f, s, cfg);
+    }
+    if let Some((at, lines)) = &s.splash
+        && at.elapsed().as_secs_f64() < cfg.splash.secs
+    {
+        let lines = lines.iter().map(|l| Line::raw(format!(" {l}"))).collect();
+        draw_popup(f, "dcsctl (any key to dismiss)", lines, 60, cfg);
+    }
+}
+
+/// Centered bordered box sized to `lines`, clipped to the screen.
+fn draw_popup(f: &mut Frame, title: &str, lines: Vec<Line<'static>>, width: u16, cfg: &Config) {
+    let full = f.area();
+    let w = width.min(full.width);
+    let h = (lines.len() + 2).min(full.height as usize) as u16;
+    let area = Rect {
+        x: full.x + (full.width - w) / 2,
+        y: full.y + (full.height - h) / 2,
+        width: w,
+        height: h,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string())
+        .border_style(cfg.focus_style(true));
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .style(cfg.style(cfg.theme.base))
+            .block(block),
+        area,
+    );
+}
+
+/// Takeoffs and landings, newest last.
+fn draw_flight_log(f: &mut Frame, s: &UiState, cfg: &Config) {
+    let events = &s.flight_log.events;
+    let lines: Vec<Line> = if events.is_empty() {
+        vec![Line::raw(" No takeoffs or landings yet.")]
+    } else {
+        let rows = f.area().height.saturating_sub(2) as usize;
+        events
+            .iter()
+            .skip(events.len().saturating_sub(rows))
+            .map(|e| Line::raw(format!(" {}  {}", fmt_utc(e.wall_ms), e.describe())))
+            .collect()
+    };
+    draw_popup(f, "Flight log (l: close, L: clear)", lines, 50, cfg);
+}
+
+/// Input health and exporter sanity checks; nothing here is a flight warning.
+fn draw_diagnostics(f: &mut Frame, s: &UiState, cfg: &Config) {
+    let n = &s.rx_stats;
+    let last = match s.seen.packet {
+        Some(at) => format!("{:.1} s ago", at.elapsed().as_secs_f64()),
+        None => "never".into(),
+    };
+    let mut lines = vec![
+        Line::raw(format!(
+            " Input     {}",
+            s.rx_from.as_deref().unwrap_or("none yet")
+        )),
+        Line::raw(format!(
+            " Packets   {} received, {} ok, {} rejected",
+            n.datagrams, n.ok, n.err
+        )),
+        Line::raw(format!(" Last      {last}")),
+    ];
+    let q = &s.seq;
+    lines.push(match q.last {
+        None => Line::raw(" Sequence  off (exporter sends no seq)"),
+        Some(last) => {
+            let pct = 100.0 * q.dropped as f64 / q.frames.saturating_add(q.dropped) as f64;
+            let line = format!(
+                " Sequence  #{last}: dropped {} ({pct:.1}%), {} late, {} restarts",
+                q.dropped, q.late, q.restarts
+            );
+            if q.dropped > 0 {
+                Line::styled(line, cfg.style(Style::default().fg(Color::Yellow)))
+            } else {
+                Line::raw(line)
+            }
+        }
+    });
+    let t = &s.last;
+    lines.push(match cfg.diagnostics.tas_mach_tolerance {
+        None => Line::raw(" TAS/Mach  off (diagnostics.tas_mach_tolerance)"),
+        Some(tol) => match (t.tas_ms, t.mach, t.alt_msl) {
+            (Some(tas), Some(mach), Some(alt)) if mach > 0.05 => {
+                let expect = mach * speed_of_sound_isa(alt);
+                let err = (tas - expect) / expect;
+                let (verdict, style) = if err.abs() > tol {
+                    ("MISMATCH", cfg.style(Style::default().fg(Color::Red).bold()))
+                } else {
+                    ("ok", Style::default())
+                };
+                Line::styled(
+                    format!(
+                        " TAS/Mach  TAS {tas:.1} m/s, M{mach:.2} implies {expect:.1} m/s ({:+.1}%) {verdict}",
+                        err * 100.0
+                    ),
+                    style,
+                )
+            }
+            _ => Line::raw(" TAS/Mach  needs tas_ms, mach > 0.05 and alt_msl"),
+        },
+    });
+    draw_popup(f, "Diagnostics (d: close)", lines, 72, cfg);
+}
 
-    // normal layout
-    f.render_widget(header_line(&s.last), layout[0]);
+/// ICAO standard atmosphere speed of sound (m/s): temperature falls 6.5 K/km to the
+/// tropopause at 11 km, then stays at 216.65 K.
+fn speed_of_sound_isa(alt_m: f64) -> f64 {
+    let temp_k = 288.15 - 0.0065 * alt_m.clamp(0.0, 11_000.0);
+    (1.4 * 287.053 * temp_k).sqrt()
+}
 
-    // top row 4 columns
-    let stats_row = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-            Constraint::Percentage(33),
-            // Constraint::Percentage(25),
-        ])
-        .split(layout[1]);
+/// ISA (troposphere) altitude at which the standard pressure equals `p_pa`.
+fn pressure_altitude(p_pa: f64) -> f64 {
+    44_330.77 * (1.0 - (p_pa / 101_325.0).powf(0.190_263))
+}
 
-    draw_one_pane(f, s, Pane::Flight, stats_row[0], false);
-    draw_one_pane(f, s, Pane::Att, stats_row[1], false);
-    draw_one_pane(f, s, Pane::Systems, stats_row[2], false);
-    // draw_one_pane(f, s, Pane::Inputs, stats_row[3], false);
+/// ISA altitude with the same air density as `oat_c` at pressure altitude `pa_m`.
+fn density_altitude(pa_m: f64, oat_c: f64) -> f64 {
+    let p = 101_325.0 * (1.0 - pa_m / 44_330.77).powf(5.255_88);
+    let rho = p / (287.053 * (oat_c + 273.15));
+    44_330.77 * (1.0 - (rho / 1.225).powf(0.234_969))
+}
 
-    // charts (full width blocks)
-    draw_one_pane(f, s, Pane::IasChart, layout[2], false);
-    draw_one_pane(f, s, Pane::AltChart, layout[3], false);
+/// A pane on a slower refresh_ms as last rendered, copied back into later frames
+/// until `due` so the pane costs nothing in between.
+#[derive(Debug, Default)]
+struct HeldPane {
+    due: bool,
+    cells: Option<Buffer>,
+}
+
+fn draw_one_pane(
+    f: &mut Frame,
+    s: &UiState,
+    cfg: &Config,
+    which: Pane,
+    area: Rect,
+    fullscreen: bool,
+) {
+    let Some(Ok(mut held)) = s.held.get(&which).map(|h| h.lock()) else {
+        draw_pane_live(f, s, cfg, which, area, fullscreen);
+        return;
+    };
+    let buf = f.buffer_mut();
+    match &held.cells {
+        // A resize or layout change moves the pane; draw it afresh then.
+        Some(cells) if !held.due && cells.area == area => {
+            for pos in area.intersection(buf.area).positions() {
+                buf[pos] = cells[pos].clone();
+            }
+        }
+        _ => {
+            draw_pane_live(f, s, cfg, which, area, fullscreen);
+            let buf = f.buffer_mut();
+            let mut cells = Buffer::empty(area);
+            for pos in area.intersection(buf.area).positions() {
+                cells[pos] = buf[pos].clone();
+            }
+            held.cells = Some(cells);
+            held.due = false;
+        }
+    }
 }
 
-fn draw_one_pane(f: &mut Frame, s: &UiState, which: Pane, area: Rect, fullscreen: bool) {
+fn draw_pane_live(
+    f: &mut Frame,
+    s: &UiState,
+    cfg: &Config,
+    which: Pane,
+    area: Rect,
+    fullscreen: bool,
+) {
     let is_focused = s.focused == which && !fullscreen;
+    let pin_mark = match (s.pinned.contains_key(&which), s.seen.stale(which)) {
+        (true, _) => " [PINNED]",
+        (false, true) => " *",
+        (false, false) => "",
+    };
+    // In single-slot mode, say which chart this is and how to get to the next.
+    let pin_mark = match s.chart_selection {
+        Some(_) if !fullscreen && CHART_ROWS.contains(&which) => {
+            let shown = s.charts_shown();
+            let n = shown.iter().position(|&p| p == which).unwrap_or(0) + 1;
+            format!("{pin_mark} [{n}/{}, t: next]", shown.len())
+        }
+        _ => pin_mark.to_string(),
+    };
+    let pin_mark = match s.extremes.title(which, s.units) {
+        Some(range) if s.show_extremes && s.focused == which => range + &pin_mark,
+        _ => pin_mark,
+    };
+    let s = s.pinned.get(&which).unwrap_or(s);
 
     match which {
         Pane::Flight => {
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title("Flight")
-                .border_style(if is_focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
-                });
-            let w = Paragraph::new(format_info_left(&s.last))
+            // HUD-style tapes either side of the text when there is room for them.
+            let area = if area.width >= TAPE_MIN_PANE_W && area.height >= 5 {
+                let cols = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Length(TAPE_W),
+                        Constraint::Min(0),
+                        Constraint::Length(TAPE_W),
+                    ])
+                    .split(area);
+                let ias = s.last.ias_ms.map(|v| v * s.units.speed_scale());
+                draw_tape(f, cols[0], s.units.speed_unit(), ias, 100.0);
+                let scale = s.units.alt_scale();
+                let alt = s.last.alt_msl.map(|v| v * scale);
+                draw_tape(f, cols[2], s.units.alt_unit(), alt, 1000.0 * scale);
+                cols[1]
+            } else {
+                area
+            };
+            let block = cfg.pane_block(format!("Flight{pin_mark}"), is_focused);
+            let text = format_info_left(
+                &s.last,
+                s.ias_trend(),
+                s.gs.get(),
+                s.alt_datum,
+                s.units,
+                &s.warnings,
+                cfg,
+            );
+            let w = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+            f.render_widget(w, area);
+        }
+        Pane::Att => {
+            let limit = cfg.limits_for(s.last.name.as_deref()).g_max;
+            let area = if area.width >= GMETER_MIN_PANE_W && area.height >= 6 {
+                let cols = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Min(0), Constraint::Length(GMETER_W)])
+                    .split(area);
+                let g = s
+                    .last
+                    .accel
+                    .as_ref()
+                    .and_then(|a| a.y)
+                    .and(s.smooth_g.get());
+                draw_gmeter(f, cols[1], g, limit, cfg);
+                cols[0]
+            } else {
+                area
+            };
+            let block = cfg.pane_block(format!("Att/Accel{pin_mark}"), is_focused);
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            let text = if inner.height >= HORIZON_MIN_INNER_H && inner.width >= HORIZON_MIN_INNER_W
+            {
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(4), Constraint::Min(0)])
+                    .split(inner);
+                draw_horizon(f, rows[1], s, cfg);
+                rows[0]
+            } else {
+                inner
+            };
+            let w =
+                Paragraph::new(format_info_right(&s.last, s, limit, cfg)).wrap(Wrap { trim: true });
+            f.render_widget(w, text);
+        }
+        Pane::Computed => {
+            let block = cfg.pane_block(format!("Computed{pin_mark}"), is_focused);
+            let w = Paragraph::new(format_computed(&s.raw, cfg))
                 .block(block)
                 .wrap(Wrap { trim: true });
             f.render_widget(w, area);
         }
-        Pane::Att => {
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title("Att/Accel")
-                .border_style(if is_focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
-                });
-            let w = Paragraph::new(format_info_right(&s.last))
+        Pane::Stats => {
+            let block = cfg.pane_block(format!("Stats{pin_mark}"), is_focused);
+            let w = Paragraph::new(format_stats(s, cfg))
                 .block(block)
                 .wrap(Wrap { trim: true });
             f.render_widget(w, area);
         }
         Pane::Systems => {
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title("Systems")
-                .border_style(if is_focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
-                });
-            let w = Paragraph::new(format_systems(&s.last))
+            let block = cfg.pane_block(format!("Systems{pin_mark}"), is_focused);
+            let w = Paragraph::new(format_systems(&s.last, &s.warnings, &s.max_split, cfg))
                 .block(block)
                 .wrap(Wrap { trim: true });
             f.render_widget(w, area);
         }
         Pane::Inputs => {
+            // Code table on top, sorted by code, then the running log.
+            let mut codes: Vec<_> = s.pad_codes.iter().collect();
+            codes.sort_by_key(|(code, _)| **code);
+            let table_h = (codes.len() as u16 + 3).min(area.height / 2);
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(table_h), Constraint::Min(0)])
+                .split(area);
+            let mut lines = vec![Line::raw(format!(
+                "{:>5}  {:<16} {:>6}  action",
+                "code", "name", "count"
+            ))];
+            lines.extend(codes.iter().map(|(code, (n, act))| {
+                Line::raw(format!(
+                    "{code:>5}  {:<16} {n:>6}  {act:?}",
+                    pad_code_name(**code)
+                ))
+            }));
+            let block = cfg.pane_block("Pad codes (W: clear, E: export)", is_focused);
+            f.render_widget(Paragraph::new(lines).block(block), rows[0]);
+            let mut area = rows[1];
+
+            // Raw ABS axes, once the pad has sent any.
+            if !s.pad_abs.is_empty() {
+                let abs_h = (s.pad_abs.len() as u16 + 3).min(area.height / 2);
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(abs_h), Constraint::Min(0)])
+                    .split(area);
+                let mut lines = vec![Line::raw(format!(
+                    "{:>5}  {:<16} {:>8} {:>8} {:>8} {:>8}",
+                    "code", "name", "value", "min", "max", "events"
+                ))];
+                lines.extend(s.pad_abs.iter().map(|(code, a)| {
+                    Line::raw(format!(
+                        "{code:>5}  {:<16} {:>8} {:>8} {:>8} {:>8}",
+                        abs_code_name(*code),
+                        a.value,
+                        a.min,
+                        a.max,
+                        a.events
+                    ))
+                }));
+                let block = cfg.pane_block("ABS axes", is_focused);
+                f.render_widget(Paragraph::new(lines).block(block), rows[0]);
+                area = rows[1];
+            }
+
             let max_lines = 16usize;
             let len = s.input_log.len();
             let start = len.saturating_sub(max_lines);
@@ -801,161 +5788,965 @@ fn draw_one_pane(f: &mut Frame, s: &UiState, which: Pane, area: Rect, fullscreen
                 .cloned()
                 .collect::<Vec<_>>()
                 .join("\n");
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title("Inputs")
-                .border_style(if is_focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
-                });
+            let block = cfg.pane_block(format!("Inputs{pin_mark}"), is_focused);
             let w = Paragraph::new(inputs_text)
                 .block(block)
                 .wrap(Wrap { trim: false });
             f.render_widget(w, area);
         }
-        Pane::IasChart => {
-            let inner = area.width.saturating_sub(2) as usize;
-            let data = last_n_scaled(&s.ias_hist, inner, 1.943_844);
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title("IAS (kt)")
-                .border_style(if is_focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
+        Pane::IasChart
+        | Pane::AltChart
+        | Pane::MachChart
+        | Pane::PsChart
+        | Pane::VvChart
+        | Pane::GChart => {
+            let Some(c) = which.chart_spec(s.units) else {
+                return;
+            };
+            // Current value after the title; the unit alone until there is one.
+            let now = match which {
+                Pane::AltChart => s.last.alt_msl.map(|m| fmt_alt(m, s.units)),
+                Pane::MachChart => Some(s.last.mach.map_or("-".into(), |m| format!("{m:.2}"))),
+                Pane::PsChart => {
+                    s.ps.get()
+                        .map(|ps| format!("{:+.0} {}", ps * c.scale, c.unit))
+                }
+                Pane::VvChart => s.last.vv_ms.map(|vv| {
+                    let prec = usize::from(c.scale == 1.0);
+                    format!("{:+.prec$} {}", vv * c.scale, c.unit)
+                }),
+                Pane::GChart => {
+                    let g = s.last.accel.as_ref().and_then(|a| a.y);
+                    Some(match (g, s.g_session) {
+                        (Some(g), Some([lo, hi])) => {
+                            format!("now {g:.1}, max {hi:.1}, min {lo:.1}")
+                        }
+                        (Some(g), None) => format!("now {g:.1}"),
+                        (None, _) => "-".into(),
+                    })
+                }
+                _ => None,
+            };
+            let now = now.unwrap_or_else(|| c.unit.to_string());
+            let block = cfg.pane_block(format!("{} ({now}){pin_mark}", c.title), is_focused);
+            draw_chart(f, area, block, s, cfg, which);
+        }
+    }
+}
+
+fn draw_chart(f: &mut Frame, area: Rect, block: Block, s: &UiState, cfg: &Config, which: Pane) {
+    let (Some(hist), Some(spec)) = (s.chart_hist(which), which.chart_spec(s.units)) else {
+        return;
+    };
+    let (times, scale) = (&s.hist_times, spec.scale);
+    let chart = cfg.chart(which);
+    // --compare always draws lines, so the second recording can go on top.
+    let overlay = s
+        .compare
+        .as_deref()
+        .and_then(|c| Some((c, c.track.chart_hist(which)?)));
+    let color = cfg.fg(cfg.colors.charts.get(which));
+    // The G trace turns red above the airframe's g_max.
+    let over = match which {
+        Pane::GChart => cfg.limits_for(s.last.name.as_deref()).g_max,
+        _ => None,
+    };
+    let over_style = cfg.style(Style::default().fg(Color::Red));
+    let span = cfg.charts.span_secs.map(Duration::from_secs_f64);
+    let now = s.chart_now();
+    let inner = area.width.saturating_sub(2) as usize;
+    let block = if chart.invert {
+        block.title(Line::from("inverted ").right_aligned())
+    } else {
+        block
+    };
+    // Centered charts name the extremes of what is on screen, since the axis has no labels.
+    let (lo, hi) = if spec.centered {
+        let samples = chart_samples(hist, times, span, now, inner, false, chart.smooth_window);
+        samples[samples.len().saturating_sub(inner)..]
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            })
+    } else {
+        (f64::NAN, f64::NAN)
+    };
+    let block = if lo.is_finite() {
+        let prec = usize::from(scale == 1.0);
+        let (lo, hi) = (lo * scale, hi * scale);
+        block.title(Line::from(format!("min {lo:+.prec$} max {hi:+.prec$} ")).right_aligned())
+    } else {
+        block
+    };
+    match (chart.style, overlay) {
+        (ChartStyle::Sparkline, None) => {
+            let samples = chart_samples(hist, times, span, now, inner, false, chart.smooth_window);
+            // Bars are integers: x100 keeps two decimals (Mach) without changing the shape.
+            // A fixed range maps onto 0..=1000 instead.
+            let (mut data, full) = match chart.range {
+                // Negative values (Ps) center the bars on zero rather than dropping out.
+                None if spec.centered || samples.iter().any(|&v| v < 0.0) => {
+                    let m = samples.iter().fold(0f64, |m, v| m.max(v.abs())).max(1e-9);
+                    let centered: Vec<f64> =
+                        samples.iter().map(|v| (v / m + 1.0) * 500.0).collect();
+                    (last_n_scaled(&centered, inner, 1.0), Some(1000))
+                }
+                None => (last_n_scaled(&samples, inner, scale * 100.0), None),
+                Some([lo, hi]) => {
+                    let fixed: Vec<f64> = samples
+                        .iter()
+                        .map(|v| ((v * scale).clamp(lo, hi) - lo) / (hi - lo) * 1000.0)
+                        .collect();
+                    (last_n_scaled(&fixed, inner, 1.0), Some(1000))
+                }
+            };
+            if chart.invert {
+                let max = full.unwrap_or_else(|| data.iter().copied().max().unwrap_or(0));
+                data.iter_mut().for_each(|v| *v = max - *v);
+            }
+            let shown = &samples[samples.len().saturating_sub(inner)..];
+            let bars = data.iter().zip(shown).map(|(&v, &raw)| {
+                let bar = SparklineBar::from(v);
+                match over {
+                    Some(limit) if raw * scale > limit => bar.style(over_style),
+                    _ => bar,
+                }
+            });
+            let mut spark = Sparkline::default().block(block).data(bars).style(color);
+            if let Some(full) = full {
+                spark = spark.max(full);
+            }
+            f.render_widget(spark, area);
+        }
+        (_, overlay) => {
+            // Each braille cell holds 2 columns of dots, so fit twice as many samples.
+            let series = |hist: &VecDeque<f64>, times: &VecDeque<Instant>| {
+                let samples =
+                    chart_samples(hist, times, span, now, inner * 2, true, chart.smooth_window);
+                let mut points = last_n_points(&samples, inner * 2, scale);
+                if let Some([lo, hi]) = chart.range {
+                    points.iter_mut().for_each(|(_, y)| *y = y.clamp(lo, hi));
+                }
+                if chart.invert {
+                    points.iter_mut().for_each(|(_, y)| *y = -*y);
+                }
+                points
+            };
+            let points = series(hist, times);
+            let other = overlay.map(|(c, h)| series(h, &c.track.hist_times));
+            let (lo, hi) = points
+                .iter()
+                .chain(other.iter().flatten())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, y)| {
+                    (lo.min(y), hi.max(y))
                 });
-            let w = Sparkline::default().block(block).data(&data);
-            f.render_widget(w, area);
+            let (lo, hi) = match chart.range {
+                Some([lo, hi]) if chart.invert => (-hi, -lo),
+                Some(range) => (range[0], range[1]),
+                // Keep zero on the middle row so climb and descent read at a glance.
+                None if spec.centered => {
+                    let m = lo.abs().max(hi.abs()).max(1.0) * 1.05;
+                    if m.is_finite() { (-m, m) } else { (-1.0, 1.0) }
+                }
+                None if lo.is_finite() => {
+                    let pad = ((hi - lo) * 0.05).max(0.005);
+                    (lo - pad, hi + pad)
+                }
+                None => (0.0, 1.0),
+            };
+            let line = |data, style| {
+                Dataset::default()
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(style)
+                    .data(data)
+            };
+            let mut datasets = vec![line(&points, color)];
+            // Dots over the line rather than a second line, which would bridge the gaps.
+            let sign = if chart.invert { -1.0 } else { 1.0 };
+            let hot: Vec<(f64, f64)> = match over {
+                Some(limit) => points
+                    .iter()
+                    .copied()
+                    .filter(|&(_, y)| y * sign > limit)
+                    .collect(),
+                None => Vec::new(),
+            };
+            if !hot.is_empty() {
+                datasets.push(line(&hot, over_style).graph_type(GraphType::Scatter));
+            }
+            let mut block = block;
+            if let (Some((c, _)), Some(other)) = (overlay, &other) {
+                // A legend wouldn't fit a short chart; name the lines in the border.
+                let b_color = cfg.fg(Some(cfg.colors.compare));
+                let [a, b] = c.names.clone();
+                let names = vec![
+                    Span::styled(a, color),
+                    " vs ".into(),
+                    Span::styled(b, b_color),
+                ];
+                block = block.title(Line::from(names).right_aligned());
+                datasets.push(line(other, b_color));
+            }
+            let chart = Chart::new(datasets)
+                .block(block)
+                .x_axis(Axis::default().bounds([0.0, (inner * 2).max(1) as f64 - 1.0]))
+                .y_axis(Axis::default().bounds([lo, hi]));
+            f.render_widget(chart, area);
         }
-        Pane::AltChart => {
-            let inner = area.width.saturating_sub(2) as usize;
-            let data = last_n(&s.alt_hist, inner);
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title("Altitude MSL (m)")
-                .border_style(if is_focused {
-                    Style::default().fg(Color::Yellow)
-                } else {
-                    Style::default()
+    }
+}
+
+/// Vertical instrument tape: a scale spanning `range` around `value`, current value boxed
+/// in the middle row. Scale labels scroll as the value moves.
+fn draw_tape(f: &mut Frame, area: Rect, title: &str, value: Option<f64>, range: f64) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string());
+    let rows = area.height.saturating_sub(2) as i64;
+    let Some(v) = value.filter(|_| rows > 0) else {
+        f.render_widget(Paragraph::new("  ---").block(block), area);
+        return;
+    };
+    let step = nice_step(range / rows as f64);
+    let base = (v / step).round() * step;
+    let mid = rows / 2;
+    let lines: Vec<String> = (0..rows)
+        .map(|r| {
+            let off = mid - r;
+            if off == 0 {
+                format!("[{v:>5.0}]")
+            } else if off % 2 == 0 {
+                format!(" {:>5.0}", base + off as f64 * step)
+            } else {
+                "     -".to_string()
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines.join("\n")).block(block), area);
+}
+
+/// Vertical load-factor bar filled from 0 G, with a tick at the configured limit. The bar
+/// goes yellow past 75% of the limit and red past 90%.
+fn draw_gmeter(f: &mut Frame, area: Rect, g: Option<f64>, limit: Option<f64>, cfg: &Config) {
+    let title = g.map(|g| format!("{g:.1}G")).unwrap_or("G".into());
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let rows = area.height.saturating_sub(2) as usize;
+    let Some(g) = g.filter(|_| rows > 0) else {
+        f.render_widget(Paragraph::new(" ---").block(block), area);
+        return;
+    };
+    let top = limit.map(|l| l * 1.2).unwrap_or(GMETER_DEFAULT_TOP).max(g);
+    let bottom = (-3f64).min(g);
+    let step = (top - bottom) / rows as f64;
+    let ratio = limit.map_or(0.0, |l| g / l);
+    let bar = cfg.style(Style::default().fg(gradient(&cfg.colors.g_gradient, ratio)));
+    let lines: Vec<Line> = (0..rows)
+        .map(|r| {
+            let hi = top - r as f64 * step;
+            let mid = hi - step / 2.0;
+            let filled = if g >= 0.0 {
+                (0.0..=g).contains(&mid)
+            } else {
+                (g..=0.0).contains(&mid)
+            };
+            let body = if filled {
+                Span::styled("███", bar)
+            } else if (hi - step..hi).contains(&0.0) {
+                Span::raw("───")
+            } else {
+                Span::raw("   ")
+            };
+            let tick = match limit {
+                Some(l) if (hi - step..hi).contains(&l) => " ◀",
+                _ => "",
+            };
+            Line::from(vec![body, Span::raw(tick)])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Attitude indicator: horizon line moved by pitch and rotated by bank around a fixed
+/// aircraft symbol. The trend vector (`o`) shows where the flight path will point in
+/// `TREND_LEAD_S` at the current rate of change, relative to the nose.
+fn draw_horizon(f: &mut Frame, area: Rect, s: &UiState, cfg: &Config) {
+    let Some(att) = &s.last.att else {
+        return;
+    };
+    let (Some(pitch), Some(bank)) = (att.pitch, att.bank) else {
+        return;
+    };
+    let scale = 1.0 / HORIZON_RANGE_DEG.to_radians();
+    // Horizon: a long line perpendicular-offset from center by -pitch, rotated by -bank.
+    let (dx, dy) = ((-bank).cos(), (-bank).sin());
+    let off = -pitch * scale;
+    let (cx, cy) = (-dy * off, dx * off);
+    let trend = s
+        .trend
+        .then(|| {
+            let fpa = s.last.fpa_rad()?;
+            let (t0, f0) = *s.fpa_hist.front()?;
+            let (t1, f1) = *s.fpa_hist.back()?;
+            let dt = t1.duration_since(t0).as_secs_f64();
+            let rate = if dt > 0.2 { (f1 - f0) / dt } else { 0.0 };
+            Some(((fpa + rate * TREND_LEAD_S - pitch) * scale).clamp(-0.95, 0.95))
+        })
+        .flatten();
+    // ratatui skips a canvas line if either end is out of bounds, so clip it first.
+    let horizon_seg = clip_unit_square(
+        (cx - 3.0 * dx, cy - 3.0 * dy),
+        (cx + 3.0 * dx, cy + 3.0 * dy),
+    );
+    let horizon = cfg.style(Style::default().fg(Color::Cyan));
+    let aircraft = cfg.style(Style::default().fg(Color::Yellow));
+    let canvas = Canvas::default()
+        .marker(Marker::Braille)
+        .x_bounds([-1.0, 1.0])
+        .y_bounds([-1.0, 1.0])
+        .paint(move |ctx| {
+            if let Some(((x1, y1), (x2, y2))) = horizon_seg {
+                ctx.draw(&CanvasLine {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color: horizon.fg.unwrap_or(Color::Reset),
                 });
-            let w = Sparkline::default().block(block).data(&data);
-            f.render_widget(w, area);
+            }
+            let wing = aircraft.fg.unwrap_or(Color::Reset);
+            for (x1, x2) in [(-0.6, -0.2), (0.2, 0.6)] {
+                ctx.draw(&CanvasLine {
+                    x1,
+                    y1: 0.0,
+                    x2,
+                    y2: 0.0,
+                    color: wing,
+                });
+            }
+            ctx.print(0.0, 0.0, Span::styled("^", aircraft));
+            if let Some(y) = trend {
+                ctx.print(0.0, y, Span::styled("o", aircraft));
+            }
+        });
+    f.render_widget(canvas, area);
+}
+
+/// Clip a segment to [-1, 1] x [-1, 1] (Liang-Barsky); `None` if it misses entirely.
+fn clip_unit_square(a: (f64, f64), b: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    for (p, q) in [
+        (-dx, a.0 + 1.0),
+        (dx, 1.0 - a.0),
+        (-dy, a.1 + 1.0),
+        (dy, 1.0 - a.1),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
         }
     }
+    (t0 <= t1).then_some((
+        (a.0 + t0 * dx, a.1 + t0 * dy),
+        (a.0 + t1 * dx, a.1 + t1 * dy),
+    ))
+}
+
+/// Round a raw step up to 1/2/5 x 10^n so tape labels land on readable numbers.
+fn nice_step(raw: f64) -> f64 {
+    if !(raw.is_finite() && raw > 0.0) {
+        return 1.0;
+    }
+    let mag = 10f64.powf(raw.log10().floor());
+    let norm = raw / mag;
+    let nice = if norm <= 1.0 {
+        1.0
+    } else if norm <= 2.0 {
+        2.0
+    } else if norm <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * mag
 }
 
 // ---------------- Formatting helpers ----------------
 
-fn header_line(t: &Telemetry) -> Paragraph<'static> {
-    let name = t.name.as_deref().unwrap_or("?");
+/// The header for `s.header` and its height, or None when hidden with nothing to report.
+fn header_line(s: &UiState, cfg: &Config) -> Option<(Paragraph<'static>, u16)> {
+    let t = &s.last;
+    let mut name = t.name.as_deref().unwrap_or("?").to_string();
+    if !s.airframes.is_empty() {
+        // Position among all tracked airframes, in the order `m` cycles them.
+        let before = s.airframes.keys().filter(|n| **n < name).count();
+        name += &format!(" ({}/{}, m: next)", before + 1, s.airframes.len() + 1);
+    }
     let lat = t.lat.map(|v| format!("{v:.5}")).unwrap_or("-".into());
     let lon = t.lon.map(|v| format!("{v:.5}")).unwrap_or("-".into());
-    Paragraph::new(format!(
-        " DCS Dash — Airframe: {name}   POS: {lat}, {lon}   Ctrl+C / q / Esc to exit "
-    ))
-    .block(Block::default().borders(Borders::ALL).title("Status"))
+    // Warnings go first so they survive truncation on narrow terminals.
+    let mut spans = vec![Span::raw(" ")];
+    spans.extend(stale_spans(s, cfg));
+    spans.extend(warning_spans(&s.warnings, cfg));
+    if let Some((at, msg)) = &s.flash
+        && at.elapsed().as_millis() < FLASH_MS
+    {
+        let style = cfg.style(Style::default().fg(Color::Black).bg(Color::Green));
+        spans.push(Span::styled(format!(" {msg} "), style));
+        spans.push(Span::raw(" "));
+    }
+    match s.header {
+        HeaderMode::Hidden if spans.len() == 1 => return None,
+        HeaderMode::Hidden => return Some((Paragraph::new(Line::from(spans)), 1)),
+        _ => {}
+    }
+    if let Some(secs) = cfg.header.stats_secs {
+        spans.push(Span::raw(format!(
+            "{}   ",
+            rolling_stats(s, secs, cfg.history())
+        )));
+    }
+    if let Some(rec) = s.recorded {
+        let part = if rec.part > 0 {
+            format!(" #{}", rec.part)
+        } else {
+            String::new()
+        };
+        let style = cfg.style(Style::default().fg(Color::Red).bold());
+        spans.push(Span::styled(format!("REC {}{part}", rec.frames), style));
+        spans.push(Span::raw("   "));
+    }
+    if let Some(r) = s.replay {
+        let mmss = |ms: u64| format!("{:02}:{:02}", ms / 60_000, ms / 1000 % 60);
+        let state = if r.finished {
+            "FINISHED".to_string()
+        } else if r.paused {
+            "PAUSED".to_string()
+        } else {
+            format!("{}x", r.speed)
+        };
+        spans.push(Span::styled(
+            format!("REPLAY {} / {} {state}", mmss(r.pos_ms), mmss(r.len_ms)),
+            cfg.style(Style::default().fg(Color::Cyan).bold()),
+        ));
+        spans.push(Span::raw("   "));
+    }
+    let n = &s.rx_stats;
+    if s.header == HeaderMode::Compact {
+        spans.push(Span::raw(format!("{name}  {} ok / {} err", n.ok, n.err)));
+        return Some((Paragraph::new(Line::from(spans)), 1));
+    }
+    let rx = s.rx_from.as_deref().unwrap_or("none yet");
+    spans.push(Span::raw(format!(
+        "DCS Dash — Airframe: {name}   POS: {lat}, {lon}   RX: {rx} ({} pkt, {} ok / {} err)   Ctrl+C / q / Esc to exit ",
+        n.datagrams, n.ok, n.err
+    )));
+    let block = Block::default().borders(Borders::ALL).title("Status");
+    Some((Paragraph::new(Line::from(spans)).block(block), 3))
+}
+
+/// "30s: IAS 280–340 kt, ALT +1200 ft": the IAS range and altitude change over the last
+/// `secs` of chart history, or placeholders until the history reaches back that far.
+fn rolling_stats(s: &UiState, secs: f64, cap: usize) -> String {
+    let (window, now) = (Duration::from_secs_f64(secs), s.chart_now());
+    let n = s
+        .hist_times
+        .iter()
+        .rev()
+        .take_while(|&&t| now.saturating_duration_since(t) <= window)
+        .count();
+    // A full buffer is as far back as it goes; use it rather than waiting forever.
+    let covered = s.hist_times.len() > n || s.hist_times.len() == cap;
+    let skip = s.ias_hist.len().saturating_sub(n);
+    let ias: Vec<f64> = s
+        .ias_hist
+        .iter()
+        .skip(skip)
+        .map(|v| v * s.units.speed_scale())
+        .collect();
+    let alt: Vec<f64> = s.alt_hist.iter().skip(skip).copied().collect();
+    match (ias.first(), alt.first(), alt.last()) {
+        (Some(_), Some(a0), Some(a1)) if covered => {
+            let lo = ias.iter().copied().fold(f64::INFINITY, f64::min);
+            let hi = ias.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let d = (a1 - a0) * s.units.alt_scale();
+            format!(
+                "{secs}s: IAS {lo:.0}–{hi:.0} {}, ALT {d:+.0} {}",
+                s.units.speed_unit(),
+                s.units.alt_unit()
+            )
+        }
+        _ => format!("{secs}s: IAS ---, ALT ---"),
+    }
 }
 
-fn format_info_left(t: &Telemetry) -> String {
+/// Altitude in the active unit; feet get comma thousands separators ("12,500 ft").
+fn fmt_alt(meters: f64, units: Units) -> String {
+    match units {
+        Units::Mixed | Units::Metric => format!("{meters:.0} m"),
+        Units::Imperial => format!("{} ft", thousands((meters * M_TO_FT).round() as i64)),
+        Units::Both => format!(
+            "{} / {}",
+            fmt_alt(meters, Units::Imperial),
+            fmt_alt(meters, Units::Metric)
+        ),
+    }
+}
+
+fn thousands(v: i64) -> String {
+    let digits = v.unsigned_abs().to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if v < 0 {
+        out.push('-');
+    }
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// `gs_ms` is the derived ground speed, if known yet; `datum` the zeroed reference altitude.
+fn format_info_left(
+    t: &Telemetry,
+    ias_trend: Option<f64>,
+    gs_ms: Option<f64>,
+    datum: Option<f64>,
+    units: Units,
+    w: &Warnings,
+    cfg: &Config,
+) -> Text<'static> {
+    let speed = units.speed_scale();
+    let unit = units.speed_unit();
+    // The other unit of the pair, in brackets after IAS.
+    let alt_units = match units {
+        Units::Metric => Units::Imperial,
+        Units::Mixed | Units::Imperial | Units::Both => Units::Metric,
+    };
     let ias_ms = t.ias_ms.unwrap_or(0.0);
-    let ias_kt = ias_ms * 1.943_844;
-    let ias_kmh = ias_ms * 3.6;
     let tas_ms = t.tas_ms.unwrap_or(0.0);
-    let tas_kt = tas_ms * 1.943_844;
     let alt = t.alt_msl.unwrap_or(0.0);
     let agl = t.alt_agl.unwrap_or(0.0);
     let mach = t.mach.unwrap_or(0.0);
     let vv = t.vv_ms.unwrap_or(0.0);
-    format!(
-        "IAS: {:>6.1} kt ({:>6.1} km/h)\nTAS: {:>6.1} kt\nALT MSL: {:>8.0} m   AGL: {:>7.0} m\nMach: {:>4.2}   VV: {:>6.1} m/s",
-        ias_kt, ias_kmh, tas_kt, alt, agl, mach, vv
-    )
+    let c = &cfg.colors.flight;
+    // Speed trend: one caret per IAS_TREND_STEPS_KT step the projection passes.
+    let caret = ias_trend.map_or(String::new(), |kt| {
+        let n = IAS_TREND_STEPS_KT
+            .iter()
+            .filter(|&&s| kt.abs() >= s)
+            .count();
+        (if kt > 0.0 { "▲" } else { "▼" }).repeat(n)
+    });
+    let mut text = Text::from(vec![
+        Line::from(vec![
+            "IAS: ".into(),
+            Span::styled(format!("{:>6.1} {unit}", ias_ms * speed), cfg.fg(c.ias)),
+            caret.into(),
+            Span::styled(
+                format!(
+                    " ({:>6.1} {})",
+                    ias_ms * alt_units.speed_scale(),
+                    alt_units.speed_unit()
+                ),
+                cfg.fg(c.ias),
+            ),
+        ]),
+        Line::from(if units == Units::Both {
+            format!(
+                "TAS: {:>6.1} {unit} ({:>6.1} km/h)",
+                tas_ms * speed,
+                tas_ms * 3.6
+            )
+        } else {
+            format!("TAS: {:>6.1} {unit}", tas_ms * speed)
+        }),
+        Line::from(format!(
+            "{unit}  IAS {:>5.0}  TAS {:>5.0}  GS {:>5}",
+            ias_ms * speed,
+            tas_ms * speed,
+            gs_ms.map_or("--".into(), |v| format!("{:.0}", v * speed))
+        )),
+    ]);
+    let agl = Span::styled(
+        format!("{:>9}", fmt_alt(agl, units)),
+        if w.low_agl {
+            cfg.style(Style::default().fg(Color::Red).bold())
+        } else {
+            Style::default()
+        },
+    );
+    let mut alt_line = vec![
+        "ALT MSL: ".into(),
+        Span::styled(format!("{:>10}", fmt_alt(alt, units)), cfg.fg(c.alt)),
+    ];
+    // Dual units are too wide to share a line in a third-width pane.
+    if units == Units::Both {
+        text.push_line(Line::from(alt_line));
+        text.push_line(Line::from(vec!["AGL:     ".into(), agl]));
+    } else {
+        alt_line.extend(["   AGL: ".into(), agl]);
+        text.push_line(Line::from(alt_line));
+    }
+    let vv = match units {
+        Units::Mixed | Units::Metric => format!("{vv:>6.1} m/s"),
+        Units::Imperial => format!("{:>6.0} fpm", vv * M_TO_FT * 60.0),
+        Units::Both => format!("{vv:>6.1} m/s / {:.0} fpm", vv * M_TO_FT * 60.0),
+    };
+    text.push_line(Line::from(vec![
+        "Mach: ".into(),
+        Span::styled(format!("{mach:>4.2}"), cfg.fg(c.mach)),
+        "   VV: ".into(),
+        Span::styled(vv, cfg.fg(c.vv)),
+    ]));
+    // Over the ground, unlike the trend vector's airmass angle, for glidepath work.
+    let fpa = match (t.vv_ms, gs_ms) {
+        (Some(vv), Some(gs)) if gs >= FPA_MIN_GS => {
+            format!(
+                "{:>+5.1}°  ({:+.1}%)",
+                vv.atan2(gs).to_degrees(),
+                vv / gs * 100.0
+            )
+        }
+        _ => "--".into(),
+    };
+    text.push_line(format!("FPA: {fpa}"));
+    // Only for exporters that send either; the DA needs OAT, and PA falls back to MSL.
+    if t.p_static_pa.is_some() || t.oat_c.is_some() {
+        let pa = t.p_static_pa.map(pressure_altitude);
+        if let Some(pa) = pa {
+            let vs_msl = t.alt_msl.map_or(String::new(), |msl| {
+                let diff = pa - msl;
+                let sign = if diff >= 0.0 { "+" } else { "" };
+                format!("  ({sign}{} vs MSL)", fmt_alt(diff, units))
+            });
+            text.push_line(format!("PA: {}{vs_msl}", fmt_alt(pa, units)));
+        }
+        let oat = t.oat_c.map_or("--".into(), |c| format!("{c:.1}°C"));
+        let da = t
+            .oat_c
+            .zip(pa.or(t.alt_msl))
+            .map_or("--".into(), |(c, pa)| {
+                fmt_alt(density_altitude(pa, c), units)
+            });
+        text.push_line(format!("OAT: {oat}   DA: {da}"));
+    }
+    if let (Some(datum), Some(alt)) = (datum, t.alt_msl) {
+        let rel = alt - datum;
+        let sign = if rel >= 0.0 { "+" } else { "" };
+        text.push_line(format!(
+            "REL: {sign}{}  (datum {})",
+            fmt_alt(rel, units),
+            fmt_alt(datum, units)
+        ));
+    }
+    text
 }
 
-fn format_info_right(t: &Telemetry) -> String {
-    let aoa_deg = t.aoa_rad.unwrap_or(0.0) * 57.295_779_5;
+/// AoA and load factor come from the display filters in `s` when the frame has them.
+/// With a G limit, the load factor is colored along colors.g_gradient. With aoa_units
+/// for the airframe, AoA is in its units with an approach indexer cue.
+fn format_info_right(
+    t: &Telemetry,
+    s: &UiState,
+    limit: Option<f64>,
+    cfg: &Config,
+) -> Text<'static> {
+    let aoa = t.aoa_rad.and(s.smooth_aoa.get()).unwrap_or(0.0);
     let (p, b, y) = match &t.att {
         Some(a) => (
-            a.pitch.unwrap_or(0.0) * 57.295_779_5,
-            a.bank.unwrap_or(0.0) * 57.295_779_5,
-            a.yaw.unwrap_or(0.0) * 57.295_779_5,
+            a.pitch.unwrap_or(0.0),
+            a.bank.unwrap_or(0.0),
+            a.yaw.unwrap_or(0.0),
         ),
         None => (0.0, 0.0, 0.0),
     };
+    let ang = |rad| s.angles.fmt(rad);
     let (ax, ay, az) = match &t.accel {
-        Some(g) => (g.x.unwrap_or(0.0), g.y.unwrap_or(0.0), g.z.unwrap_or(0.0)),
+        Some(g) => (
+            g.x.unwrap_or(0.0),
+            g.y.and(s.smooth_g.get()).unwrap_or(0.0),
+            g.z.unwrap_or(0.0),
+        ),
         None => (0.0, 0.0, 0.0),
     };
+    let g_style = match limit {
+        Some(l) => cfg.style(Style::default().fg(gradient(&cfg.colors.g_gradient, ay / l))),
+        None => Style::default(),
+    };
+    let aoa_line = match cfg.aoa_units_for(t.name.as_deref()) {
+        Some(u) if s.aoa_units => {
+            let v = u.units(aoa);
+            let mut spans = vec![Span::raw(format!("AoA: {v:>6.1} u  "))];
+            // Indexer colors: green slow chevron, amber on-speed donut, red fast chevron.
+            let [lo, hi] = u.on_speed;
+            let (cue, color) = if v > hi {
+                ("v SLOW", Color::Green)
+            } else if v < lo {
+                ("^ FAST", Color::Red)
+            } else {
+                ("o ON SPEED", Color::Yellow)
+            };
+            if t.aoa_rad.is_some() {
+                spans.push(Span::styled(
+                    cue,
+                    cfg.style(Style::default().fg(color).bold()),
+                ));
+            }
+            Line::from(spans)
+        }
+        _ => Line::from(format!("AoA: {}", ang(aoa))),
+    };
+    Text::from(vec![
+        aoa_line,
+        Line::from(format!(
+            "Pitch: {}  Bank: {}  Yaw: {}",
+            ang(p),
+            ang(b),
+            ang(y)
+        )),
+        Line::from(vec![
+            format!("Accel G: X {ax:>5.2}  Y ").into(),
+            Span::styled(format!("{ay:>5.2}"), g_style),
+            format!("  Z {az:>5.2}").into(),
+        ]),
+    ])
+}
+
+/// Flight + attitude essentials condensed onto one line.
+fn format_status_line(t: &Telemetry) -> String {
+    let num = |v: Option<f64>, prec: usize| {
+        v.map(|x| format!("{x:.prec$}"))
+            .unwrap_or_else(|| "---".into())
+    };
     format!(
-        "AoA: {:>5.2}°\nPitch: {:>6.2}°  Bank: {:>6.2}°  Yaw: {:>6.2}°\nAccel G: X {:>5.2}  Y {:>5.2}  Z {:>5.2}",
-        aoa_deg, p, b, y, ax, ay, az
+        "IAS {} kt  ALT {} m  M {}  AoA {}°  G {}",
+        num(t.ias_ms.map(|v| v * 1.943_844), 0),
+        num(t.alt_msl, 0),
+        num(t.mach, 2),
+        num(t.aoa_rad.map(|v| v * 57.295_779_5), 1),
+        num(t.accel.as_ref().and_then(|a| a.y), 1),
     )
 }
 
-fn fmt_pair_opt(label: &str, p: &Option<Pair>, scale_pct: bool) -> Option<String> {
-    let to = p.as_ref()?;
-    if to.L.is_none() && to.R.is_none() {
-        return None;
+/// A Systems "label: L x  R y" row whose values are already formatted ("---" if missing),
+/// so column widths can be worked out across all rows before padding.
+struct PairRow {
+    label: String,
+    l: String,
+    r: String,
+}
+
+impl PairRow {
+    /// None when neither side reports. `est` marks the values as estimated.
+    fn new(label: &str, p: &Option<Pair>, scale_pct: bool, est: bool) -> Option<PairRow> {
+        let to = p.as_ref()?;
+        if to.L.is_none() && to.R.is_none() {
+            return None;
+        }
+        let scale = if scale_pct { 100.0 } else { 1.0 };
+        let fmtv =
+            |v: Option<f64>| v.map_or("---".into(), |x| est_mark(format!("{:.1}", x * scale), est));
+        Some(PairRow {
+            label: label.to_string(),
+            l: fmtv(to.L),
+            r: fmtv(to.R),
+        })
+    }
+
+    fn empty(label: &str) -> PairRow {
+        PairRow {
+            label: label.to_string(),
+            l: "---".into(),
+            r: "---".into(),
+        }
+    }
+
+    /// A row behind an exporter `*_present` flag: false drops it even if old values
+    /// linger in the merged state, true keeps it as "---" until values arrive.
+    fn gated(
+        label: &str,
+        p: &Option<Pair>,
+        scale_pct: bool,
+        present: Option<bool>,
+    ) -> Option<PairRow> {
+        match present {
+            Some(false) => None,
+            Some(true) => Some(
+                PairRow::new(label, p, scale_pct, false).unwrap_or_else(|| PairRow::empty(label)),
+            ),
+            None => PairRow::new(label, p, scale_pct, false),
+        }
+    }
+}
+
+/// Prefix a formatted value with "~" when the exporter says it's estimated or guessed.
+fn est_mark(v: String, est: bool) -> String {
+    if est { format!("~{v}") } else { v }
+}
+
+/// Pad labels and values to the widest in their column so L and R line up.
+fn align_pair_rows(rows: &[PairRow]) -> Vec<String> {
+    let lw = rows
+        .iter()
+        .map(|r| r.label.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let vw = rows
+        .iter()
+        .flat_map(|r| [r.l.len(), r.r.len()])
+        .max()
+        .unwrap_or(0);
+    rows.iter()
+        .map(|r| {
+            let label = format!("{}:", r.label);
+            format!("{label:<lw$} L {:>vw$}  R {:>vw$}", r.l, r.r)
+        })
+        .collect()
+}
+
+/// One line per [computed] entry.
+fn format_computed(raw: &Value, cfg: &Config) -> Text<'static> {
+    if cfg.computed.is_empty() {
+        return Text::raw("No [computed] fields configured.");
+    }
+    let w = cfg
+        .computed
+        .keys()
+        .map(|k| k.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let lines: Vec<Line> = cfg
+        .computed
+        .iter()
+        .map(|(name, c)| {
+            let label = format!("{name}:");
+            let value = match &c.0 {
+                Ok(e) => e.eval(raw).map_or("---".into(), |v| format!("{v:.2}")),
+                Err(_) => "invalid".into(),
+            };
+            Line::raw(format!("{label:<w$} {value:>10}"))
+        })
+        .collect();
+    Text::from(lines)
+}
+
+/// What reaches `ingest`: rates, totals and the latest rejected frame.
+fn format_stats(s: &UiState, cfg: &Config) -> Text<'static> {
+    let n = &s.rx_stats;
+    let (fps, bps) = n.rates();
+    let mut text = Text::from(vec![
+        Line::raw(format!("Rate:    {fps:>5.1} pkt/s  {}/s", fmt_bytes(bps))),
+        Line::raw(format!(
+            "Total:   {} reads, {}",
+            n.datagrams,
+            fmt_bytes(n.bytes as f64)
+        )),
+        Line::raw(format!("Frames:  {} ok", n.ok)),
+    ]);
+    let err = format!("Errors:  {}", n.err);
+    text.push_line(if n.err > 0 {
+        Line::styled(err, cfg.style(Style::default().fg(Color::Red)))
+    } else {
+        Line::raw(err)
+    });
+    if let Some(e) = &n.last_error {
+        let ago = SystemTime::now()
+            .duration_since(e.wall)
+            .unwrap_or_default()
+            .as_secs_f64();
+        text.push_line(format!(
+            "Last:    {} ({ago:.0}s ago)",
+            fmt_utc(unix_ms(e.wall))
+        ));
+        text.push_line(format!("Why:     {}", e.reason));
+        text.push_line(format!("Frame:   {}", e.excerpt));
     }
-    let mut l = to.L;
-    let mut r = to.R;
-    if scale_pct {
-        l = l.map(|x| x * 100.0);
-        r = r.map(|x| x * 100.0);
+    text
+}
+
+/// "812 B", "3.4 KiB", "1.2 MiB".
+fn fmt_bytes(b: f64) -> String {
+    if b < 1024.0 {
+        format!("{b:.0} B")
+    } else if b < 1024.0 * 1024.0 {
+        format!("{:.1} KiB", b / 1024.0)
+    } else {
+        format!("{:.1} MiB", b / (1024.0 * 1024.0))
     }
-    let fmtv = |v: Option<f64>| v.map(|x| format!("{:>6.1}", x)).unwrap_or("   ---".into());
-    Some(format!("{label}: L {}  R {}", fmtv(l), fmtv(r)))
 }
 
-fn format_systems(t: &Telemetry) -> String {
-    let mut lines = Vec::new();
+fn format_systems(t: &Telemetry, w: &Warnings, max: &MaxSplit, cfg: &Config) -> Text<'static> {
+    // A flight-only exporter: placeholders would suggest a twin with everything dead.
+    if t.engine.is_none() && t.mech.is_none() && t.fuel_kg.is_none() {
+        return Text::raw("No systems data");
+    }
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    // Whether any value shown carries the "~" mark, for the legend.
+    let mut estimated = false;
 
     if let Some(e) = &t.engine {
-        if let Some(s) = fmt_pair_opt("RPM %", &e.rpm, false) {
-            lines.push(s);
-        }
-        let thr_label = if e.thrtl_est.unwrap_or(false) {
-            "THR % (est)"
-        } else {
-            "THR %"
-        };
-        lines.push(
-            fmt_pair_opt(thr_label, &e.thrtl, true)
-                .unwrap_or_else(|| format!("{thr_label}: L   ---  R   ---")),
+        // Collect the pair rows first; alignment needs every row's widths.
+        let mut rows = Vec::new();
+        rows.extend(PairRow::new("RPM %", &e.rpm, false, false));
+        let thr_est = e.thrtl_est.unwrap_or(false);
+        estimated |= thr_est && e.thrtl.is_some();
+        rows.push(
+            PairRow::new("THR %", &e.thrtl, true, thr_est)
+                .unwrap_or_else(|| PairRow::empty("THR %")),
         );
-        if e.noz_present.unwrap_or(false) {
-            lines.push(
-                fmt_pair_opt("NOZ %", &e.noz, true)
-                    .unwrap_or_else(|| "NOZ %: L   ---  R   ---".into()),
-            );
-        }
-        if let Some(s) = fmt_pair_opt("TEMP", &e.temp, false) {
-            lines.push(s);
-        }
-        if let Some(s) = fmt_pair_opt("FF", &e.fuelf, false) {
-            lines.push(s);
-        }
-        if e.map_present.unwrap_or(false) {
-            lines.push(
-                fmt_pair_opt("MAP", &e.map, false)
-                    .unwrap_or_else(|| "MAP: L   ---  R   ---".into()),
-            );
+        rows.extend(PairRow::gated("NOZ %", &e.noz, true, e.noz_present));
+        rows.extend(PairRow::new("TEMP", &e.temp, false, false));
+        rows.extend(PairRow::new("FF", &e.fuelf, false, false));
+        rows.extend(PairRow::gated("MAP", &e.map, false, e.map_present));
+        lines.extend(align_pair_rows(&rows).into_iter().map(Line::from));
+        // Twins only: L − R and the session's largest split.
+        for (label, p, warn, max) in [
+            ("ΔRPM", &e.rpm, w.rpm_split, max.rpm),
+            ("ΔTEMP", &e.temp, w.temp_split, max.temp),
+        ] {
+            if let Some(d) = p.as_ref().and_then(Pair::split) {
+                let style = if warn {
+                    cfg.style(Style::default().fg(Color::Red).bold())
+                } else {
+                    Style::default()
+                };
+                let max = max.unwrap_or(d.abs());
+                lines.push(Line::styled(
+                    format!("{label}: {d:>+6.1}  max {max:.1}"),
+                    style,
+                ));
+            }
         }
     }
 
-    lines.push(String::new());
-
-    let show = |label: &str, v: Option<f64>, guessed: bool| -> String {
-        let lab = if guessed {
-            format!("{label} (guess)")
+    if let Some(fuel) = t.fuel_kg {
+        let style = if w.low_fuel {
+            cfg.style(Style::default().fg(Color::Red).bold())
         } else {
-            label.to_string()
+            Style::default()
         };
+        lines.push(Line::styled(format!("FUEL: {fuel:>7.0} kg"), style));
+    }
+
+    lines.push(Line::default());
+
+    let show = |label: &str, v: Option<f64>, guessed: bool| -> Line<'static> {
         match v {
-            Some(x) => format!("{lab}: {:>5.2}", x),
-            None => format!("{lab}:   ---"),
+            Some(x) => format!("{label}: {:>5}", est_mark(format!("{x:.2}"), guessed)),
+            None => format!("{label}:   ---"),
         }
+        .into()
     };
 
     if let Some(m) = &t.mech {
@@ -964,7 +6755,9 @@ fn format_systems(t: &Telemetry) -> String {
         lines.push(show("Airbrk", m.airbrake, false));
         lines.push(show("Hook", m.hook, false));
         lines.push(show("Wing", m.wing, false));
-        lines.push(show("WoW", m.wow, m.wow_guess.unwrap_or(false)));
+        let wow_guess = m.wow_guess.unwrap_or(false);
+        estimated |= wow_guess && m.wow.is_some();
+        lines.push(show("WoW", m.wow, wow_guess));
     } else {
         lines.extend(
             [
@@ -975,9 +6768,301 @@ fn format_systems(t: &Telemetry) -> String {
                 "Wing:   ---",
                 "WoW:    ---",
             ]
-            .map(str::to_string),
+            .map(Line::from),
+        );
+    }
+    if estimated {
+        lines.push(Line::styled(
+            "~ estimated by the exporter",
+            cfg.style(Style::default().dim()),
+        ));
+    }
+
+    Text::from(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stat_ignores_missing_and_nan() {
+        let mut s = Stat::default();
+        for v in [
+            Some(2.0),
+            None,
+            Some(f64::NAN),
+            Some(-1.0),
+            Some(f64::INFINITY),
+            Some(5.0),
+        ] {
+            s.add(v);
+        }
+        assert_eq!(s.n, 3);
+        assert_eq!((s.min, s.max), (-1.0, 5.0));
+        assert_eq!(s.avg(), Some(2.0));
+        assert_eq!(Stat::default().avg(), None);
+    }
+
+    #[test]
+    fn summary_line() {
+        let empty = Summary::default().line();
+        assert_eq!(
+            empty,
+            "n=0  IAS --- kt  ALT --- m  M ---  G --- (min ---)  RPM --- %"
+        );
+        let mut sum = Summary::default();
+        for (ias, g) in [(100.0, 1.0), (200.0, -0.5)] {
+            let t: Telemetry = serde_json::from_value(serde_json::json!({
+                "ias_ms": ias / 1.943_844,
+                "alt_msl": 1000.0,
+                "accel": { "y": g },
+            }))
+            .unwrap();
+            sum.add(&t);
+        }
+        assert_eq!(
+            sum.line(),
+            "n=2  IAS 150/200 kt  ALT 1000/1000 m  M ---  G 0.2/1.0 (min -0.5)  RPM --- %"
+        );
+    }
+
+    #[test]
+    fn telemetry_round_trip() {
+        let full = serde_json::json!({
+            "name": "F-16C_50", "lat": 43.7, "lon": 44.0, "alt_msl": 3000.0,
+            "alt_agl": 2500.0, "ias_ms": 150.0, "tas_ms": 170.0, "mach": 0.5,
+            "aoa_rad": 0.05, "vv_ms": 9.5, "fuel_kg": 2000.0, "p_static_pa": 70000.0,
+            "oat_c": -5.0, "seq": 42,
+            "att": { "pitch": 0.1, "bank": -0.2, "yaw": 1.5 },
+            "accel": { "x": 0.1, "y": 2.5, "z": 0.0 },
+            "engine": {
+                "rpm": { "L": 92.0, "R": 91.0 }, "thrtl": { "L": 0.8, "R": 0.8 },
+                "thrtl_est": true, "noz": { "L": 0.3 }, "noz_present": true,
+                "temp": { "L": 640.0 }, "fuelf": { "L": 1.2 }, "map": { "L": 30.0 },
+                "map_present": false,
+            },
+            "mech": {
+                "gear": 1.0, "flaps": 0.5, "airbrake": 0.0, "hook": 0.0, "wing": 0.0,
+                "wow": 1.0, "wow_guess": false,
+            },
+        });
+        let t = Telemetry::deserialize(&full).unwrap();
+        let out = serde_json::to_value(&t).unwrap();
+        assert_eq!(out, full);
+        let again = serde_json::to_value(Telemetry::deserialize(&out).unwrap()).unwrap();
+        assert_eq!(again, out);
+
+        // Unset fields are left out rather than written as null.
+        let sparse = serde_json::json!({ "ias_ms": 150.0, "engine": { "rpm": { "L": 92.0 } } });
+        let out = serde_json::to_value(Telemetry::deserialize(&sparse).unwrap()).unwrap();
+        assert_eq!(out, sparse);
+    }
+
+    #[test]
+    fn exp_filter_step_response() {
+        let t0 = Instant::now();
+        let tau = Duration::from_millis(500);
+        let mut f = ExpFilter::default();
+        assert_eq!(f.update(0.0, t0, tau), 0.0);
+        let y = f.update(10.0, t0 + tau, tau);
+        assert!((y - 10.0 * (1.0 - (-1f64).exp())).abs() < 1e-9, "{y}");
+        assert!((6.2..6.4).contains(&y));
+
+        let mut raw = ExpFilter::default();
+        raw.update(0.0, t0, Duration::ZERO);
+        assert_eq!(raw.update(10.0, t0 + tau, Duration::ZERO), 10.0);
+        assert_eq!(raw.get(), Some(10.0));
+    }
+
+    #[test]
+    fn config_template_parses_clean() {
+        let cfg: Config = toml::from_str(CONFIG_TEMPLATE).unwrap();
+        assert_eq!(cfg.problems(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn bad_computed_is_a_config_problem() {
+        let cfg: Config =
+            toml::from_str("[computed]\nok = \"accel.y - 1\"\nbad = \"accel.y +* (\"").unwrap();
+        let problems = cfg.problems();
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(problems[0].starts_with("computed.bad: must be an expression"));
+    }
+
+    #[test]
+    fn seq_stats_gaps_and_restarts() {
+        let mut q = SeqStats::default();
+        for seq in [10, 11, 14, 13, 20] {
+            q.update(seq);
+        }
+        assert_eq!((q.dropped, q.late, q.restarts), (7, 1, 0));
+        q.update(u64::MAX);
+        q.update(0);
+        assert_eq!((q.dropped, q.late, q.restarts), (7, 1, 1));
+        assert_eq!(q.last, Some(0));
+
+        let mut q = SeqStats::default();
+        q.update(u16::MAX as u64 - 1);
+        q.update(1);
+        assert_eq!((q.dropped, q.restarts), (2, 0));
+    }
+
+    fn framed(payload: &[u8]) -> Vec<u8> {
+        let mut out = (payload.len() as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(payload);
+        out
+    }
+
+    #[test]
+    fn length_frames_back_to_back() {
+        let mut bytes = framed(br#"{"ias_ms":1}"#);
+        bytes.extend(framed(b"{\n\"mach\":0.5\n}"));
+        let (frames, clean) = length_frames(&bytes);
+        assert_eq!(
+            frames,
+            [&br#"{"ias_ms":1}"#[..], &b"{\n\"mach\":0.5\n}"[..]]
+        );
+        assert!(clean);
+    }
+
+    #[test]
+    fn length_frames_truncated_prefix() {
+        let mut bytes = framed(b"{}");
+        bytes.extend_from_slice(&[0, 0, 1]);
+        let (frames, clean) = length_frames(&bytes);
+        assert_eq!(frames, [&b"{}"[..]]);
+        assert!(!clean);
+    }
+
+    #[test]
+    fn length_frames_overlong_length() {
+        let mut bytes = framed(b"{}");
+        bytes.extend_from_slice(&[0, 0, 0, 9]);
+        bytes.extend_from_slice(b"{\"a\":1}");
+        let (frames, clean) = length_frames(&bytes);
+        assert_eq!(frames, [&b"{}"[..]]);
+        assert!(!clean);
+    }
+
+    #[test]
+    fn length_frames_zero_length() {
+        let mut bytes = framed(b"");
+        bytes.extend(framed(b"{}"));
+        let (frames, clean) = length_frames(&bytes);
+        assert_eq!(frames, [&b""[..], &b"{}"[..]]);
+        assert!(clean);
+        assert_eq!(length_frames(&[]), (vec![], true));
+    }
+
+    fn apply(state: &mut UiState, patch: Value) {
+        let t = Telemetry::deserialize(&patch).unwrap();
+        apply_telemetry(state, t, patch, &Config::default(), None);
+    }
+
+    #[test]
+    fn partial_flight_packet_holds_chart_values() {
+        let mut state = UiState::default();
+        apply(
+            &mut state,
+            serde_json::json!({ "ias_ms": 150.0, "alt_msl": 3000.0, "mach": 0.5 }),
         );
+        apply(
+            &mut state,
+            serde_json::json!({ "alt_msl": 3100.0, "vv_ms": 5.0 }),
+        );
+        assert_eq!(state.ias_hist, [150.0, 150.0]);
+        assert_eq!(state.alt_hist, [3000.0, 3100.0]);
+        assert_eq!(state.mach_hist, [0.5, 0.5]);
+        assert_eq!(state.vv_hist, [0.0, 5.0]);
+    }
+
+    #[test]
+    fn replay_burst_charts_on_recording_time() {
+        let cfg: Config = toml::from_str("[charts]\nsample_ms = 500").unwrap();
+        let mut state = UiState::default();
+        let epoch = Instant::now();
+        // 3 s of 10 Hz frames, all applied at once as a seek does.
+        for i in 0..30 {
+            let patch = serde_json::json!({ "ias_ms": 100.0 + i as f64 });
+            let t = Telemetry::deserialize(&patch).unwrap();
+            let at = epoch + Duration::from_millis(i * 100);
+            apply_telemetry(&mut state, t, patch, &cfg, Some(at));
+        }
+        assert_eq!(state.hist_times.len(), 6);
+        let span = state.hist_times[5].duration_since(state.hist_times[0]);
+        assert_eq!(span, Duration::from_millis(2500));
+        // Each sample averages the frames since the previous one.
+        assert_eq!(state.ias_hist[0], 100.0);
+        assert_eq!(state.ias_hist[1], 103.0);
+    }
+
+    #[test]
+    fn left_right_reach_the_whole_top_row() {
+        use Pane::*;
+        let mut state = UiState {
+            top_row: vec![Flight, Att, Systems, Computed, Stats],
+            focused: Flight,
+            ..UiState::default()
+        };
+        state.hidden.insert(Att);
+        let mut seen = vec![];
+        for _ in 0..4 {
+            state.pad_action(PadAction::Right);
+            seen.push(state.focused);
+        }
+        assert_eq!(seen, [Systems, Computed, Stats, Flight]);
+        state.pad_action(PadAction::Left);
+        assert_eq!(state.focused, Stats);
+        state.focused = IasChart;
+        state.pad_action(PadAction::Left);
+        assert_eq!(state.focused, IasChart);
+    }
+
+    #[test]
+    fn mech_settles_only_on_mech_packets() {
+        let cfg: Config = toml::from_str("[flight_log]\nmech = true").unwrap();
+        let mut state = UiState::default();
+        let mut feed = |patch: Value| {
+            let t = Telemetry::deserialize(&patch).unwrap();
+            apply_telemetry(&mut state, t, patch, &cfg, None);
+        };
+        feed(serde_json::json!({ "mech": { "gear": 0.0 } }));
+        feed(serde_json::json!({ "mech": { "gear": 0.0 } }));
+        // Gear halfway down, with flight-only packets in between.
+        feed(serde_json::json!({ "mech": { "gear": 0.5 } }));
+        feed(serde_json::json!({ "ias_ms": 100.0 }));
+        feed(serde_json::json!({ "ias_ms": 100.0 }));
+        feed(serde_json::json!({ "mech": { "gear": 1.0 } }));
+        feed(serde_json::json!({ "mech": { "gear": 1.0 } }));
+        let logged: Vec<_> = state
+            .flight_log
+            .events
+            .iter()
+            .map(|e| format!("{:?}", e.kind))
+            .collect();
+        assert_eq!(logged.len(), 1, "{logged:?}");
+        assert!(logged[0].contains("pos: 1.0"), "{logged:?}");
     }
 
-    lines.join("\n")
+    #[test]
+    fn flight_packet_keeps_engine_data() {
+        let mut state = UiState::default();
+        apply(
+            &mut state,
+            serde_json::json!({
+                "name": "F-16C_50",
+                "engine": { "rpm": { "L": 92.5 }, "temp": { "L": 640.0 } },
+            }),
+        );
+        let engine = serde_json::to_value(&state.last.engine).unwrap();
+        let raw = state.raw["engine"].clone();
+        apply(
+            &mut state,
+            serde_json::json!({ "name": "F-16C_50", "ias_ms": 150.0, "alt_msl": 3000.0 }),
+        );
+        assert_eq!(serde_json::to_value(&state.last.engine).unwrap(), engine);
+        assert_eq!(state.raw["engine"], raw);
+        assert_eq!(state.last.ias_ms, Some(150.0));
+    }
 }